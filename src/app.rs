@@ -1,102 +1,644 @@
 use std::{
-    fs::DirEntry,
+    fs,
     path::{Path, PathBuf},
     process::Command,
+    time::{Duration, Instant},
 };
 
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use log::{debug, error};
-use ratatui::widgets::ListState;
+use ratatui::{
+    layout::{Position, Rect},
+    widgets::ListState,
+};
 
-use crate::{entry, path, status_bar::status_string};
+use crate::{
+    bookmarks::Bookmarks,
+    columns,
+    command::{self, AppCommand},
+    config::Config,
+    entry,
+    finder::Finder,
+    frecency::Frecency,
+    icons,
+    keymap::{Action, KeyBinding, KeyLookup, Keymap},
+    pane::Pane,
+    path,
+    preview::Preview,
+    search::ContentSearch,
+    status_bar::status_string,
+    tab::Tab,
+    theme::{self, Theme, DEFAULT_THEME},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum AppState {
     Running,
+    Filtering,
+    Finding,
+    Searching,
+    EnteringPath,
+    EnteringCommand,
+    NamingBookmark,
+    Bookmarks,
+    Jumping,
+    Help,
     Exit,
 }
 
-pub struct DirListState {
-    pub state: ListState,
-    pub items: Vec<DirEntry>,
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PaneLayout {
+    Single,
+    Dual,
+    Miller,
 }
 
-impl DirListState {
-    fn new(items: Vec<DirEntry>) -> Self {
-        Self {
-            state: ListState::default(),
-            items,
-        }
-    }
+// two clicks on the same entry within this interval count as a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
 
-    pub fn set_items(&mut self, items: Vec<DirEntry>) {
-        self.items = items;
-        self.state = ListState::default();
-    }
+// where the last frame drew things, so mouse events can be mapped back onto them
+#[derive(Default)]
+pub struct ClickAreas {
+    pub title_path: Rect,
+    pub panes: [Option<Rect>; 2],
 }
 
 pub struct App {
     pub app_state: AppState,
-    pub current_dir: PathBuf,
-    pub parent_dir: PathBuf,
-    pub dir_items: DirListState,
-    pub show_hidden: bool,
+    pub tabs: Vec<Tab>,
+    pub active_tab: usize,
+    pub pane_layout: PaneLayout,
+    pub show_preview: bool,
+    // icons need a patched font, so they're off unless turned on in the config or at runtime
+    pub show_icons: bool,
+    pub theme: Theme,
+    // kept so `:theme` without a name can reload the theme file after it's been edited
+    theme_name: String,
     pub status_text: String,
+    pub status_message: Option<String>,
     pub clipboard: Option<PathBuf>,
+    pub finder: Option<Finder>,
+    pub content_search: Option<ContentSearch>,
+    pub prompt_input: String,
+    pub prompt_completions: Vec<String>,
+    pub command_history: Vec<String>,
+    pub help_state: ListState,
+    command_history_idx: Option<usize>,
+    pub bookmarks: Bookmarks,
+    pub frecency: Frecency,
+    pub click_areas: ClickAreas,
+    last_click: Option<(Instant, PathBuf)>,
+    pub keymap: Keymap,
+    pub pending_keys: Vec<KeyBinding>,
+    pub pending_count: Option<usize>,
 }
 
 impl App {
     pub fn new<T: AsRef<Path>>(init_dir: T) -> Self {
+        // a broken config falls back to the defaults, with the errors shown in the status bar
+        let mut config_errors = Vec::new();
+        let config = Config::load_default().unwrap_or_else(|e| {
+            config_errors.push(e);
+            Config::default()
+        });
+        let keymap = Keymap::with_config(&config.keymap).unwrap_or_else(|e| {
+            config_errors.push(e);
+            Keymap::default()
+        });
+        // before any directory is loaded, as that's when the icons are picked
+        icons::set_overrides(&config.icons);
+        let mut tab = Tab::new(&init_dir);
+        if let Some(column_names) = &config.columns {
+            match columns::parse_columns(column_names) {
+                Ok(columns) => tab
+                    .panes
+                    .iter_mut()
+                    .for_each(|x| x.columns = columns.clone()),
+                Err(e) => config_errors.push(e),
+            }
+        }
+        let theme_name = config.theme.unwrap_or(String::from(DEFAULT_THEME));
+        let theme = Theme::load_default(&theme_name).unwrap_or_else(|e| {
+            config_errors.push(e);
+            Theme::default()
+        });
+        config_errors.iter().for_each(|e| error!("{e}"));
+
         App {
             app_state: AppState::Running,
-            current_dir: init_dir.as_ref().to_path_buf(),
-            parent_dir: path::get_parent_dir(&init_dir),
-            dir_items: DirListState::new(path::get_dir_items(&init_dir, &false)),
-            show_hidden: false,
+            tabs: vec![tab],
+            active_tab: 0,
+            pane_layout: PaneLayout::Single,
+            show_preview: false,
+            show_icons: config.show_icons,
+            theme,
+            theme_name,
             status_text: String::from("Hello There"),
+            status_message: (!config_errors.is_empty()).then(|| config_errors.join(", ")),
             clipboard: None,
+            finder: None,
+            content_search: None,
+            prompt_input: String::new(),
+            prompt_completions: Vec::new(),
+            command_history: Vec::new(),
+            help_state: ListState::default(),
+            command_history_idx: None,
+            bookmarks: Bookmarks::load_default(),
+            frecency: Frecency::load_default(),
+            click_areas: ClickAreas::default(),
+            last_click: None,
+            keymap,
+            pending_keys: Vec::new(),
+            pending_count: None,
         }
     }
 
+    pub fn tab(&self) -> &Tab {
+        &self.tabs[self.active_tab]
+    }
+
+    pub fn tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.active_tab]
+    }
+
+    pub fn pane(&self) -> &Pane {
+        self.tab().pane()
+    }
+
+    pub fn pane_mut(&mut self) -> &mut Pane {
+        self.tab_mut().pane_mut()
+    }
+
     pub fn handle_keypress(&mut self, key: KeyEvent) {
+        match self.app_state {
+            AppState::Filtering => self.handle_filter_keypress(key),
+            AppState::Finding => self.handle_finder_keypress(key),
+            AppState::Searching => self.handle_search_keypress(key),
+            AppState::EnteringPath => self.handle_path_prompt_keypress(key),
+            AppState::EnteringCommand => self.handle_command_keypress(key),
+            AppState::NamingBookmark => self.handle_bookmark_prompt_keypress(key),
+            AppState::Bookmarks => self.handle_bookmarks_keypress(key),
+            AppState::Jumping => self.handle_jump_keypress(key),
+            AppState::Help => self.handle_help_keypress(key),
+            _ => self.handle_browse_keypress(key),
+        }
+        self.update_status_bar();
+    }
+
+    // keys are collected until they either match a binding or can no longer lead to one,
+    // with any digits typed first used as a count for the motions
+    fn handle_browse_keypress(&mut self, key: KeyEvent) {
+        let key = KeyBinding::from(key);
+        if let KeyCode::Char(c @ '0'..='9') = key.code {
+            let is_count = self.pending_keys.is_empty()
+                && key.modifiers.is_empty()
+                && (c != '0' || self.pending_count.is_some());
+            if is_count {
+                let digit = c.to_digit(10).unwrap_or(0) as usize;
+                let count = self.pending_count.unwrap_or(0);
+                self.pending_count = Some(count.saturating_mul(10).saturating_add(digit));
+                return;
+            }
+        }
+
+        self.pending_keys.push(key);
+        match self.keymap.lookup(&self.pending_keys) {
+            KeyLookup::Pending => return,
+            KeyLookup::Matched(action) => {
+                let count = self.pending_count;
+                self.run_action(action, count);
+            }
+            KeyLookup::Unbound => (),
+        }
+        self.pending_keys.clear();
+        self.pending_count = None;
+    }
+
+    // the count only applies to motions, everything else runs once
+    fn run_action(&mut self, action: Action, count: Option<usize>) {
+        let repeat = count.unwrap_or(1) as isize;
+        match action {
+            Action::Quit => self.quit_app(),
+            Action::ToggleHidden => self.pane_mut().toggle_hidden(),
+            Action::CycleSort => {
+                let mut sort_order = self.pane().sort_order;
+                sort_order.mode = sort_order.mode.next();
+                self.pane_mut().set_sort_order(sort_order);
+            }
+            Action::ToggleSortReverse => {
+                let mut sort_order = self.pane().sort_order;
+                sort_order.reverse = !sort_order.reverse;
+                self.pane_mut().set_sort_order(sort_order);
+            }
+            Action::ToggleDirsFirst => {
+                let mut sort_order = self.pane().sort_order;
+                sort_order.dirs_first = !sort_order.dirs_first;
+                self.pane_mut().set_sort_order(sort_order);
+            }
+            Action::CycleLayout => self.toggle_layout(),
+            Action::TogglePreview => self.toggle_preview(),
+            Action::PreviewPageDown => self.pane_mut().preview.page_down(),
+            Action::PreviewPageUp => self.pane_mut().preview.page_up(),
+            Action::OpenCommandLine => self.open_command_line(),
+            Action::ShowHelp => self.open_help(),
+            Action::StartFilter => self.start_filter(),
+            Action::OpenFinder => self.open_finder(),
+            Action::OpenContentSearch => self.open_content_search(),
+            Action::OpenPathPrompt => self.open_path_prompt(),
+            Action::OpenBookmarkPrompt => self.open_bookmark_prompt(),
+            Action::OpenBookmarks => self.open_bookmarks(),
+            Action::OpenJump => self.open_jump(),
+            Action::ToggleTreeMode => self.pane_mut().toggle_tree_mode(),
+            Action::ToggleLongListing => self.pane_mut().toggle_long_listing(),
+            Action::ToggleIcons => self.show_icons = !self.show_icons,
+            Action::ToggleExpanded => self.pane_mut().toggle_expanded(),
+            Action::OpenTab => self.open_tab(),
+            Action::CloseTab => self.close_tab(),
+            Action::NextTab => self.cycle_tabs(true),
+            Action::PrevTab => self.cycle_tabs(false),
+            Action::Copy => self.add_selected_to_clipboard(),
+            Action::Paste => self.copy_from_clipboard(),
+            Action::Move => self.move_from_clipboard(),
+            Action::Trash => self.trash_selected(),
+            Action::GoBack => self.go_back(),
+            Action::GoForward => self.go_forward(),
+            Action::CursorUp => self.pane_mut().move_cursor_by(-repeat),
+            Action::CursorDown => self.pane_mut().move_cursor_by(repeat),
+            // like vim, a count goes to that line instead of the top or bottom
+            Action::CursorTop | Action::CursorBottom => match count {
+                Some(line_number) => self.pane_mut().move_cursor_to(line_number.max(1) - 1),
+                None if action == Action::CursorTop => self.pane_mut().move_cursor_to(0),
+                None => self.pane_mut().move_cursor_to(usize::MAX),
+            },
+            Action::HalfPageUp => self.pane_mut().move_cursor_pages(-0.5 * repeat as f32),
+            Action::HalfPageDown => self.pane_mut().move_cursor_pages(0.5 * repeat as f32),
+            Action::PageUp => self.pane_mut().move_cursor_pages(-(repeat as f32)),
+            Action::PageDown => self.pane_mut().move_cursor_pages(repeat as f32),
+            Action::SwitchPanes => self.switch_panes(),
+            Action::Open => self.open_selected(),
+            Action::NavUp => self.nav_up_dir(),
+        }
+    }
+
+    // while filtering, typed characters go to the filter instead of being treated as commands
+    fn handle_filter_keypress(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => {
+                let mut filter = self.pane().filter.clone();
+                filter.push(c);
+                self.pane_mut().set_filter(filter);
+            }
+            KeyCode::Backspace => {
+                let mut filter = self.pane().filter.clone();
+                filter.pop();
+                self.pane_mut().set_filter(filter);
+            }
+            KeyCode::Up => self.pane_mut().move_cursor_up(),
+            KeyCode::Down => self.pane_mut().move_cursor_down(),
+            KeyCode::Enter => self.app_state = AppState::Running,
+            KeyCode::Esc => {
+                self.pane_mut().set_filter(String::new());
+                self.app_state = AppState::Running;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_finder_keypress(&mut self, key: KeyEvent) {
+        let Some(finder) = &mut self.finder else {
+            self.app_state = AppState::Running;
+            return;
+        };
+
+        match key.code {
+            KeyCode::Char(c) => {
+                let mut query = finder.query.clone();
+                query.push(c);
+                finder.set_query(query);
+            }
+            KeyCode::Backspace => {
+                let mut query = finder.query.clone();
+                query.pop();
+                finder.set_query(query);
+            }
+            KeyCode::Up => finder.move_cursor_up(),
+            KeyCode::Down => finder.move_cursor_down(),
+            KeyCode::Enter => {
+                if let Some(selected_path) = finder.selected_path() {
+                    self.jump_to_path(selected_path);
+                }
+                self.close_finder();
+            }
+            KeyCode::Esc => self.close_finder(),
+            _ => {}
+        }
+    }
+
+    // enter starts the search, or jumps to the selected result once there are results
+    fn handle_search_keypress(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.content_search else {
+            self.app_state = AppState::Running;
+            return;
+        };
+
+        match key.code {
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                search.toggle_regex()
+            }
+            KeyCode::Char(c) => {
+                let mut query = search.query.clone();
+                query.push(c);
+                search.set_query(query);
+            }
+            KeyCode::Backspace => {
+                let mut query = search.query.clone();
+                query.pop();
+                search.set_query(query);
+            }
+            KeyCode::Up => search.move_cursor_up(),
+            KeyCode::Down => search.move_cursor_down(),
+            KeyCode::Enter => match search.selected_result() {
+                Some(result) => {
+                    let result_path = result.path.clone();
+                    let line_idx = result.line_number.saturating_sub(1);
+                    self.jump_to_line(result_path, line_idx);
+                    self.close_content_search();
+                }
+                None if !search.is_searching => search.start(),
+                None => (),
+            },
+            KeyCode::Esc if search.is_searching => search.cancel(),
+            KeyCode::Esc => self.close_content_search(),
+            _ => {}
+        }
+    }
+
+    fn handle_path_prompt_keypress(&mut self, key: KeyEvent) {
         match key.code {
-            KeyCode::Char('q') => self.quit_app(),
-            KeyCode::Char('h') => self.toggle_hidden(),
-            KeyCode::Char('c') | KeyCode::Char('y') => self.add_selected_to_clipboard(),
-            KeyCode::Char('p') => self.copy_from_clipboard(),
-            KeyCode::Char('x') => self.move_from_clipboard(),
-            KeyCode::Delete => self.trash_selected(),
-            KeyCode::Up => self.move_cursor_up(),
-            KeyCode::Down => self.move_cursor_down(),
-            KeyCode::Tab | KeyCode::BackTab => self.switch_panes(),
-            KeyCode::Enter | KeyCode::Char(' ') | KeyCode::Right => self.open_selected(),
-            KeyCode::Esc | KeyCode::Backspace | KeyCode::Left => self.nav_up_dir(),
+            KeyCode::Char(c) => {
+                self.prompt_input.push(c);
+                self.prompt_completions.clear();
+            }
+            KeyCode::Backspace => {
+                self.prompt_input.pop();
+                self.prompt_completions.clear();
+            }
+            KeyCode::Tab => self.complete_path_prompt(),
+            KeyCode::Enter => {
+                let input = self.prompt_input.clone();
+                self.close_path_prompt();
+                self.go_to_path(&input);
+            }
+            KeyCode::Esc => self.close_path_prompt(),
+            _ => {}
+        }
+    }
+
+    // up and down go through previously run commands, like a shell
+    fn handle_command_keypress(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => {
+                self.prompt_input.push(c);
+                self.prompt_completions.clear();
+            }
+            KeyCode::Backspace if self.prompt_input.is_empty() => self.close_command_line(),
+            KeyCode::Backspace => {
+                self.prompt_input.pop();
+                self.prompt_completions.clear();
+            }
+            KeyCode::Tab => self.complete_command_line(),
+            KeyCode::Up => self.browse_command_history(true),
+            KeyCode::Down => self.browse_command_history(false),
+            KeyCode::Enter => {
+                let input = self.prompt_input.clone();
+                self.close_command_line();
+                self.run_command(&input);
+            }
+            KeyCode::Esc => self.close_command_line(),
+            _ => {}
+        }
+    }
+
+    fn handle_bookmark_prompt_keypress(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => self.prompt_input.push(c),
+            KeyCode::Backspace => {
+                self.prompt_input.pop();
+            }
+            KeyCode::Enter => {
+                let name = self.prompt_input.trim().to_string();
+                self.app_state = AppState::Running;
+                if !name.is_empty() {
+                    let current_dir = self.pane().current_dir.clone();
+                    self.status_message = Some(match self.bookmarks.add(&name, &current_dir) {
+                        Ok(_) => format!("Bookmarked {current_dir:?} as {name}"),
+                        Err(e) => e,
+                    });
+                }
+            }
+            KeyCode::Esc => self.app_state = AppState::Running,
+            _ => {}
+        }
+    }
+
+    // typing the name of a single character bookmark jumps straight to it
+    fn handle_bookmarks_keypress(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.bookmarks.move_cursor_up(),
+            KeyCode::Down => self.bookmarks.move_cursor_down(),
+            KeyCode::Delete => self.bookmarks.remove_selected(),
+            KeyCode::Enter => {
+                let bookmark_path = self.bookmarks.selected().map(|x| x.path.clone());
+                self.app_state = AppState::Running;
+                if let Some(bookmark_path) = bookmark_path {
+                    self.go_to_bookmark(bookmark_path);
+                }
+            }
+            KeyCode::Char(c) => {
+                let bookmark = self.bookmarks.find(&c.to_string());
+                if let Some(bookmark_path) = bookmark.map(|x| x.path.clone()) {
+                    self.app_state = AppState::Running;
+                    self.go_to_bookmark(bookmark_path);
+                }
+            }
+            KeyCode::Esc => self.app_state = AppState::Running,
+            _ => {}
+        }
+    }
+
+    fn handle_help_keypress(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.help_state.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.help_state.select_next(),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+                self.app_state = AppState::Running
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_jump_keypress(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => {
+                let mut query = self.frecency.query.clone();
+                query.push(c);
+                self.frecency.set_query(query);
+            }
+            KeyCode::Backspace => {
+                let mut query = self.frecency.query.clone();
+                query.pop();
+                self.frecency.set_query(query);
+            }
+            KeyCode::Up => self.frecency.move_cursor_up(),
+            KeyCode::Down => self.frecency.move_cursor_down(),
+            KeyCode::Enter => {
+                let selected_dir = self.frecency.selected_path();
+                self.app_state = AppState::Running;
+                if let Some(selected_dir) = selected_dir {
+                    self.jump_to_dir(selected_dir);
+                }
+            }
+            KeyCode::Esc => self.app_state = AppState::Running,
+            _ => {}
+        }
+    }
+
+    // popups and prompts take the keyboard, so the mouse only works while browsing
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.app_state != AppState::Running {
+            return;
+        }
+
+        let position = Position::new(mouse.column, mouse.row);
+        let pane_idx = self
+            .click_areas
+            .panes
+            .iter()
+            .position(|x| x.is_some_and(|area| area.contains(position)));
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.click_areas.title_path.contains(position) {
+                    let title_column = mouse.column - self.click_areas.title_path.x;
+                    self.click_title_path(title_column as usize);
+                } else if let Some(pane_idx) = pane_idx {
+                    self.click_pane(pane_idx, position);
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                if let Some(pane_idx) = pane_idx {
+                    self.tab_mut().panes[pane_idx].move_cursor_down();
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                if let Some(pane_idx) = pane_idx {
+                    self.tab_mut().panes[pane_idx].move_cursor_up();
+                }
+            }
             _ => {}
         }
         self.update_status_bar();
     }
 
+    fn click_pane(&mut self, pane_idx: usize, position: Position) {
+        let Some(pane_area) = self.click_areas.panes[pane_idx] else {
+            return;
+        };
+        self.tab_mut().active_pane = pane_idx;
+
+        // the first and last rows of the pane are its border
+        if position.y <= pane_area.y || position.y >= pane_area.bottom() - 1 {
+            return;
+        }
+        let row = (position.y - pane_area.y - 1) as usize;
+        if !self.pane_mut().select_row(row) {
+            return;
+        }
+
+        let selected_path = self.pane().selected_entry().map(|x| x.path());
+        let is_double_click = match (&self.last_click, &selected_path) {
+            (Some((click_time, click_path)), Some(selected_path)) => {
+                click_time.elapsed() < DOUBLE_CLICK_INTERVAL && click_path == selected_path
+            }
+            _ => false,
+        };
+        if is_double_click {
+            self.last_click = None;
+            self.open_selected();
+        } else {
+            self.last_click = selected_path.map(|x| (Instant::now(), x));
+        }
+    }
+
+    // clicking a segment of the path in the title goes to that directory
+    fn click_title_path(&mut self, title_column: usize) {
+        let current_dir = self.pane().current_dir.to_string_lossy().to_string();
+        let Some((clicked_idx, _)) = current_dir.char_indices().nth(title_column) else {
+            return;
+        };
+        let segment_end = current_dir[clicked_idx..]
+            .find('/')
+            .map_or(current_dir.len(), |x| clicked_idx + x);
+        let segment_path = match &current_dir[..segment_end] {
+            "" => "/",
+            segment_path => segment_path,
+        };
+
+        if Path::new(segment_path) != self.pane().current_dir {
+            self.enter_dir(segment_path);
+        }
+    }
+
+    // called on every tick of the event loop to pick up the results of background work
+    // returns whether anything arrived that needs drawing
+    pub fn tick(&mut self) -> bool {
+        let finder_changed = self.finder.as_mut().is_some_and(|x| x.poll());
+        let search_changed = self.content_search.as_mut().is_some_and(|x| x.poll());
+        finder_changed || search_changed
+    }
+
     pub fn refresh_dirlist(&mut self) {
-        self.dir_items
-            .set_items(path::get_dir_items(&self.current_dir, &self.show_hidden));
-        self.auto_select_first();
+        self.pane_mut().refresh_dirlist();
         self.update_status_bar();
     }
 
-    fn auto_select_first(&mut self) {
-        match self.dir_items.state.selected() {
-            Some(_) => (),
-            None => {
-                self.dir_items.state.select_first();
-            }
+    // file operations can change the contents of any pane, so every tab is reloaded
+    fn refresh_all_panes(&mut self) {
+        for pane in self.tabs.iter_mut().flat_map(|x| x.panes.iter_mut()) {
+            pane.refresh_dirlist();
         }
+        self.update_status_bar();
     }
 
+    // a status message, such as an error, replaces the entry details until the next keypress
     fn update_status_bar(&mut self) {
-        if let Some(idx) = self.dir_items.state.selected() {
-            if idx < self.dir_items.items.len() {
-                self.status_text = status_string(self.dir_items.items[idx].path());
+        if self.app_state == AppState::Filtering {
+            self.status_text = format!("/{}", self.pane().filter);
+        } else if self.app_state == AppState::NamingBookmark {
+            self.status_text = format!("bookmark as: {}", self.prompt_input);
+        } else if matches!(
+            self.app_state,
+            AppState::EnteringPath | AppState::EnteringCommand
+        ) {
+            self.status_text = match self.app_state {
+                AppState::EnteringPath => format!("cd: {}", self.prompt_input),
+                _ => format!(":{}", self.prompt_input),
+            };
+            if self.prompt_completions.len() > 1 {
+                self.status_text
+                    .push_str(&format!("  [{}]", self.prompt_completions.join(" ")));
             }
+        } else if let Some(status_message) = self.status_message.take() {
+            self.status_text = status_message;
+        } else if self.pending_count.is_some() || !self.pending_keys.is_empty() {
+            // like vim, show what has been typed so far of a count or key sequence
+            self.status_text = self.pending_count.map_or(String::new(), |x| x.to_string());
+            for key in self.pending_keys.iter() {
+                if let KeyCode::Char(c) = key.code {
+                    self.status_text.push(c);
+                }
+            }
+        } else if let Some(selected_entry) = self.pane().selected_entry() {
+            self.status_text = status_string(selected_entry.path());
         }
     }
 
@@ -104,110 +646,443 @@ impl App {
         self.app_state = AppState::Exit;
     }
 
-    fn toggle_hidden(&mut self) {
-        self.show_hidden = !self.show_hidden;
-        self.dir_items
-            .set_items(path::get_dir_items(&self.current_dir, &self.show_hidden));
-        self.auto_select_first();
+    fn toggle_layout(&mut self) {
+        // the single and miller layouts show whichever pane was last focused
+        self.pane_layout = match self.pane_layout {
+            PaneLayout::Single => PaneLayout::Dual,
+            PaneLayout::Dual => PaneLayout::Miller,
+            PaneLayout::Miller => PaneLayout::Single,
+        };
+    }
+
+    // the miller layout always shows a preview in its last column, so this only affects the
+    // single and dual layouts
+    fn toggle_preview(&mut self) {
+        self.show_preview = !self.show_preview;
+    }
+
+    fn start_filter(&mut self) {
+        self.app_state = AppState::Filtering;
+    }
+
+    fn open_finder(&mut self) {
+        let pane = self.pane();
+        self.finder = Some(Finder::new(&pane.current_dir, pane.show_hidden));
+        self.app_state = AppState::Finding;
+    }
+
+    fn close_finder(&mut self) {
+        // dropping the finder also stops its walk
+        self.finder = None;
+        self.app_state = AppState::Running;
+    }
+
+    fn open_content_search(&mut self) {
+        let pane = self.pane();
+        self.content_search = Some(ContentSearch::new(&pane.current_dir, pane.show_hidden));
+        self.app_state = AppState::Searching;
+    }
+
+    fn close_content_search(&mut self) {
+        // dropping the search also cancels it if it is still running
+        self.content_search = None;
+        self.app_state = AppState::Running;
+    }
+
+    // like `jump_to_path`, but also brings up the preview of the file starting at `line_idx`
+    fn jump_to_line<T: AsRef<Path>>(&mut self, selected_path: T, line_idx: usize) {
+        self.jump_to_path(&selected_path);
+        let syntax_theme = self.theme.preview_syntax_theme.clone();
+        let pane = self.pane_mut();
+        pane.preview = Preview::load_at_line(
+            selected_path,
+            line_idx,
+            &pane.show_hidden,
+            &pane.sort_order,
+            &syntax_theme,
+        );
+        self.show_preview = true;
+    }
+
+    fn open_path_prompt(&mut self) {
+        self.prompt_input.clear();
+        self.prompt_completions.clear();
+        self.app_state = AppState::EnteringPath;
+    }
+
+    fn close_path_prompt(&mut self) {
+        self.app_state = AppState::Running;
+    }
+
+    fn complete_path_prompt(&mut self) {
+        let pane = self.pane();
+        self.prompt_completions =
+            path::complete_dir(&self.prompt_input, &pane.current_dir, &pane.show_hidden);
+        self.apply_prompt_completions("");
+    }
+
+    // completes as much as all the completions share, `input_prefix` being the part of the
+    // input that isn't being completed
+    fn apply_prompt_completions(&mut self, input_prefix: &str) {
+        if let Some(first_completion) = self.prompt_completions.first() {
+            let mut common_prefix = first_completion.clone();
+            for completion in self.prompt_completions.iter().skip(1) {
+                while !completion.starts_with(&common_prefix) {
+                    common_prefix.pop();
+                }
+            }
+            if input_prefix.len() + common_prefix.len() > self.prompt_input.len() {
+                self.prompt_input = format!("{input_prefix}{common_prefix}");
+            }
+        }
+    }
+
+    fn open_help(&mut self) {
+        self.help_state.select_first();
+        self.app_state = AppState::Help;
+    }
+
+    fn open_command_line(&mut self) {
+        self.prompt_input.clear();
+        self.prompt_completions.clear();
+        self.command_history_idx = None;
+        self.app_state = AppState::EnteringCommand;
+    }
+
+    fn close_command_line(&mut self) {
+        self.app_state = AppState::Running;
+    }
+
+    // the command name is completed first, then directories for `cd`
+    fn complete_command_line(&mut self) {
+        match self.prompt_input.split_once(' ') {
+            None => {
+                self.prompt_completions = command::complete_command_name(&self.prompt_input);
+                self.apply_prompt_completions("");
+            }
+            Some(("cd", dir_input)) => {
+                let pane = self.pane();
+                self.prompt_completions =
+                    path::complete_dir(dir_input, &pane.current_dir, &pane.show_hidden);
+                self.apply_prompt_completions("cd ");
+            }
+            Some(("theme", name_input)) => {
+                self.prompt_completions = theme::theme_names(theme::themes_dir())
+                    .into_iter()
+                    .filter(|x| x.starts_with(name_input))
+                    .collect();
+                self.apply_prompt_completions("theme ");
+            }
+            Some(_) => (),
+        }
+    }
+
+    fn browse_command_history(&mut self, older: bool) {
+        if self.command_history.is_empty() {
+            return;
+        }
+        let history_len = self.command_history.len();
+        self.command_history_idx = match (self.command_history_idx, older) {
+            (None, true) => Some(history_len - 1),
+            (None, false) => None,
+            (Some(idx), true) => Some(idx.saturating_sub(1)),
+            (Some(idx), false) if idx + 1 < history_len => Some(idx + 1),
+            (Some(_), false) => None,
+        };
+        self.prompt_input = match self.command_history_idx {
+            Some(idx) => self.command_history[idx].clone(),
+            None => String::new(),
+        };
+        self.prompt_completions.clear();
+    }
+
+    fn run_command(&mut self, input: &str) {
+        if input.trim().is_empty() {
+            return;
+        }
+        if self.command_history.last().is_none_or(|x| x != input) {
+            self.command_history.push(input.to_string());
+        }
+
+        let app_command = match command::parse_command(input) {
+            Ok(app_command) => app_command,
+            Err(e) => {
+                self.status_message = Some(e);
+                return;
+            }
+        };
+        match app_command {
+            AppCommand::Action(action) => self.run_action(action, None),
+            AppCommand::Cd(dir) => self.go_to_path(&dir),
+            AppCommand::Mkdir(dir_names) => {
+                self.create_entries(&dir_names, |x| fs::create_dir_all(x))
+            }
+            AppCommand::Touch(file_names) => {
+                self.create_entries(&file_names, |x| entry::touch_file(x))
+            }
+            AppCommand::Rename(new_name) => self.rename_selected(&new_name),
+            AppCommand::Hidden(show_hidden) => {
+                let pane = self.pane_mut();
+                pane.show_hidden = show_hidden.unwrap_or(!pane.show_hidden);
+                pane.refresh_dirlist();
+            }
+            AppCommand::Sort(sort_mode) => {
+                let mut sort_order = self.pane().sort_order;
+                sort_order.mode = sort_mode;
+                self.pane_mut().set_sort_order(sort_order);
+            }
+            // picking the columns is only useful in the long listing, so it's switched on too
+            AppCommand::Columns(columns) => {
+                let pane = self.pane_mut();
+                pane.long_listing = true;
+                pane.set_columns(columns);
+            }
+            AppCommand::Theme(theme_name) => self.load_theme(theme_name),
+        }
+    }
+
+    // the current theme is kept when the new one can't be loaded
+    fn load_theme(&mut self, theme_name: Option<String>) {
+        let theme_name = theme_name.unwrap_or(self.theme_name.clone());
+        match Theme::load_default(&theme_name) {
+            Ok(theme) => {
+                // previews are highlighted when loaded, so they have to be redone in the new colors
+                if theme.preview_syntax_theme != self.theme.preview_syntax_theme {
+                    self.tabs
+                        .iter_mut()
+                        .flat_map(|x| x.panes.iter_mut())
+                        .for_each(|x| x.preview = Preview::new());
+                }
+                self.theme = theme;
+                self.status_message = Some(format!("Loaded theme {theme_name}"));
+                self.theme_name = theme_name;
+            }
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    // the last created entry is selected, when it is in the current directory
+    fn create_entries<F>(&mut self, entry_names: &[String], create_entry: F)
+    where
+        F: Fn(&Path) -> std::io::Result<()>,
+    {
+        let mut created_path = None;
+        for entry_name in entry_names {
+            let entry_path = match path::expand_path(entry_name, &self.pane().current_dir) {
+                Ok(entry_path) => entry_path,
+                Err(e) => {
+                    self.status_message = Some(format!("Unable to create {entry_name}, {e}"));
+                    break;
+                }
+            };
+            match create_entry(&entry_path) {
+                Ok(_) => created_path = Some(entry_path),
+                Err(e) => {
+                    self.status_message = Some(format!("Unable to create {entry_name}, {e}"));
+                    break;
+                }
+            }
+        }
+        self.refresh_all_panes();
+        if let Some(created_path) = created_path {
+            self.pane_mut().select_path(created_path);
+        }
+    }
+
+    fn rename_selected(&mut self, new_name: &str) {
+        let Some(selected_path) = self.pane().selected_entry().map(|x| x.path()) else {
+            return;
+        };
+        match entry::rename_entry(&selected_path, new_name) {
+            Ok(renamed_path) => {
+                self.refresh_all_panes();
+                self.pane_mut().select_path(renamed_path);
+            }
+            Err(e) => self.status_message = Some(format!("Unable to rename, {e}")),
+        }
+    }
+
+    // directories are opened, for anything else its directory is opened with it selected
+    fn go_to_path(&mut self, input: &str) {
+        let expanded_path = path::expand_path(input, &self.pane().current_dir)
+            .and_then(|x| fs::canonicalize(x).map_err(|e| format!("{input}: {e}")));
+
+        match expanded_path {
+            Ok(selected_path) if selected_path.is_dir() => self.enter_dir(selected_path),
+            Ok(selected_path) => self.jump_to_path(selected_path),
+            Err(e) => self.status_message = Some(format!("Unable to go to path, {e}")),
+        }
+    }
+
+    fn open_bookmark_prompt(&mut self) {
+        self.prompt_input.clear();
+        self.app_state = AppState::NamingBookmark;
     }
 
-    fn move_cursor_up(&mut self) {
-        self.dir_items.state.select_previous();
+    fn open_bookmarks(&mut self) {
+        self.bookmarks.state.select_first();
+        self.app_state = AppState::Bookmarks;
     }
 
-    fn move_cursor_down(&mut self) {
-        self.dir_items.state.select_next();
+    fn open_jump(&mut self) {
+        self.frecency.set_query(String::new());
+        self.app_state = AppState::Jumping;
+    }
+
+    // directories that have since been removed are dropped from the database
+    fn jump_to_dir(&mut self, selected_dir: PathBuf) {
+        if selected_dir.is_dir() {
+            self.enter_dir(selected_dir);
+        } else {
+            self.frecency.remove(&selected_dir);
+            self.status_message = Some(format!("Directory {selected_dir:?} no longer exists"));
+        }
+    }
+
+    fn go_to_bookmark<T: AsRef<Path>>(&mut self, bookmark_path: T) {
+        let bookmark_path = bookmark_path.as_ref();
+        if bookmark_path.is_dir() {
+            self.enter_dir(bookmark_path);
+        } else {
+            self.status_message = Some(format!(
+                "Bookmarked directory {bookmark_path:?} no longer exists"
+            ));
+        }
+    }
+
+    // navigates to the directory containing `selected_path` with the entry itself selected
+    fn jump_to_path<T: AsRef<Path>>(&mut self, selected_path: T) {
+        let selected_path = selected_path.as_ref();
+        self.enter_dir(path::get_parent_dir(selected_path));
+        self.pane_mut().select_path(selected_path);
+    }
+
+    // directory changes made by the user go through here and the other navigation methods, so every
+    // directory visited ends up in the frecency database
+    fn enter_dir<T: AsRef<Path>>(&mut self, selected_dir: T) {
+        self.pane_mut().enter_dir(&selected_dir);
+        self.frecency.record(selected_dir);
+    }
+
+    fn nav_up_dir(&mut self) {
+        if self.pane_mut().nav_up_dir() {
+            let current_dir = self.pane().current_dir.clone();
+            self.frecency.record(current_dir);
+        }
+    }
+
+    fn go_back(&mut self) {
+        if self.pane_mut().go_back() {
+            let current_dir = self.pane().current_dir.clone();
+            self.frecency.record(current_dir);
+        }
+    }
+
+    fn go_forward(&mut self) {
+        if self.pane_mut().go_forward() {
+            let current_dir = self.pane().current_dir.clone();
+            self.frecency.record(current_dir);
+        }
     }
 
     fn switch_panes(&mut self) {
-        // TODO implement when two column pane is implemented
+        if self.pane_layout == PaneLayout::Dual {
+            self.tab_mut().switch_panes();
+        }
+    }
+
+    // a new tab starts out in the directory of the current one, with each of its panes set up
+    // like the matching pane of the current tab
+    fn open_tab(&mut self) {
+        let current_dir = self.pane().current_dir.clone();
+        let mut tab = Tab::new(&current_dir);
+        tab.active_pane = self.tab().active_pane;
+        for (pane, current_pane) in tab.panes.iter_mut().zip(&self.tab().panes) {
+            pane.show_hidden = current_pane.show_hidden;
+            pane.sort_order = current_pane.sort_order;
+            pane.long_listing = current_pane.long_listing;
+            pane.columns = current_pane.columns.clone();
+            pane.refresh_dirlist();
+        }
+        self.tabs.insert(self.active_tab + 1, tab);
+        self.active_tab += 1;
+    }
+
+    fn close_tab(&mut self) {
+        if self.tabs.len() == 1 {
+            self.status_message = Some(String::from("Unable to close the last tab"));
+            return;
+        }
+        self.tabs.remove(self.active_tab);
+        if self.active_tab == self.tabs.len() {
+            self.active_tab -= 1;
+        }
+    }
+
+    fn cycle_tabs(&mut self, forward: bool) {
+        let tab_count = self.tabs.len();
+        self.active_tab = if forward {
+            (self.active_tab + 1) % tab_count
+        } else {
+            (self.active_tab + tab_count - 1) % tab_count
+        };
     }
 
     fn add_selected_to_clipboard(&mut self) {
-        match self.dir_items.state.selected() {
-            Some(idx) => {
+        match self.pane().selected_entry().map(|x| x.path()) {
+            Some(selected_path) => {
                 // replace what is currently in the clipboard
                 // considering possibly making it a stack/list in the future
-                self.clipboard = Some(self.dir_items.items[idx].path());
-                debug!("Added {:?} to clipboard", &self.dir_items.items[idx])
+                debug!("Added {selected_path:?} to clipboard");
+                self.clipboard = Some(selected_path);
             }
             None => debug!("No item selected to be added to clipboard."),
         }
     }
 
     fn move_from_clipboard(&mut self) {
-        if self.clipboard.is_some() {
-            //should not panic since clipboard is checked to ensure it contains some value
-            let src_path = self.clipboard.as_ref().unwrap();
-            if src_path.is_file() {
-                let dest_path = entry::append_duplicates(src_path, &self.current_dir);
-
-                entry::copy_file(src_path, &dest_path, true);
-            } else if src_path.is_dir() {
-                // for directories, a merge will be attempted
-                // in the future, maybe give the user the option on whether or not to merge
-                match entry::copy_dir(src_path, &self.current_dir, true) {
-                    Ok(_) => (),
-                    Err(e) => error!("{e:}"),
-                }
-            }
-        }
-        self.refresh_dirlist();
+        self.paste_from_clipboard(true);
     }
 
     fn copy_from_clipboard(&mut self) {
-        if self.clipboard.is_some() {
-            //should not panic since clipboard is checked to ensure it contains some value
-            let src_path = self.clipboard.as_ref().unwrap();
+        self.paste_from_clipboard(false);
+    }
+
+    fn paste_from_clipboard(&mut self, move_contents: bool) {
+        if let Some(src_path) = &self.clipboard {
+            let current_dir = &self.pane().paste_dir();
             if src_path.is_file() {
-                let dest_path = entry::append_duplicates(src_path, &self.current_dir);
+                let dest_path = entry::append_duplicates(src_path, current_dir);
 
-                entry::copy_file(src_path, &dest_path, false);
+                entry::copy_file(src_path, &dest_path, move_contents);
             } else if src_path.is_dir() {
                 // for directories, a merge will be attempted
                 // in the future, maybe give the user the option on whether or not to merge
-                match entry::copy_dir(src_path, &self.current_dir, false) {
+                match entry::copy_dir(src_path, current_dir, move_contents) {
                     Ok(_) => (),
                     Err(e) => error!("{e:}"),
                 }
             }
         }
-        self.refresh_dirlist();
+        self.refresh_all_panes();
     }
 
     fn trash_selected(&mut self) {
-        if let Some(idx) = self.dir_items.state.selected() {
-            let selected_entry = &self.dir_items.items[idx];
+        if let Some(selected_entry) = self.pane().selected_entry() {
             entry::trash_entry(selected_entry);
-            self.refresh_dirlist();
-        }
-    }
-
-    fn nav_up_dir(&mut self) {
-        let new_current_dirpath = self.parent_dir.clone();
-        let new_parent_dirpath = path::get_parent_dir(&new_current_dirpath);
-
-        // only navigate up if the current directory is not the root directory
-        if new_parent_dirpath != self.current_dir {
-            self.parent_dir = new_parent_dirpath;
-            self.current_dir = new_current_dirpath;
-
-            self.refresh_dirlist();
+            self.refresh_all_panes();
         }
     }
 
     fn open_selected(&mut self) {
-        let selected_idx = self.dir_items.state.selected().unwrap_or_else(|| 0);
-        let selected_entry = &self.dir_items.items[selected_idx];
-
-        if selected_entry.metadata().unwrap().is_dir() {
-            let new_parent_dir = self.current_dir.clone();
-            self.current_dir = selected_entry.path();
-            self.parent_dir = new_parent_dir.to_owned();
-            self.refresh_dirlist();
-        } else {
-            let selected_entry_path = selected_entry.path().to_str().unwrap().to_owned();
+        let Some(selected_entry) = self.pane().selected_entry() else {
+            return;
+        };
+        let selected_entry_path = selected_entry.path();
 
+        if selected_entry_path.is_dir() {
+            self.enter_dir(selected_entry_path);
+        } else {
             // TODO handle opening files on Windows/Mac
             match Command::new("xdg-open").arg(selected_entry_path).output() {
                 Ok(_) => (),
@@ -220,6 +1095,7 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{columns::Column, sort::SortMode};
     use std::env;
     use std::fs;
     use tempfile::tempdir;
@@ -241,9 +1117,14 @@ mod tests {
         println!("Test setup...");
         let init_dir =
             env::current_dir().expect("Invalid permissions or currenty directory doesn't exists.");
-        TestContext {
-            app: App::new(init_dir),
-        }
+        let mut app = App::new(init_dir);
+        // keep the tests from touching the user's own bookmarks and visited directories,
+        // or depending on their keymap
+        app.bookmarks = Bookmarks::load(None);
+        app.frecency = Frecency::load(None);
+        app.keymap = Keymap::default();
+        app.status_message = None;
+        TestContext { app }
     }
 
     #[test]
@@ -274,13 +1155,13 @@ mod tests {
         let _test_file = fs::File::create(&test_filepath).unwrap();
 
         let mut test_app = setup();
-        test_app.app.current_dir = test_dirpath.to_path_buf();
-        test_app.app.parent_dir = test_dirpath.parent().unwrap().to_path_buf();
+        test_app.app.pane_mut().current_dir = test_dirpath.to_path_buf();
+        test_app.app.pane_mut().parent_dir = test_dirpath.parent().unwrap().to_path_buf();
         test_app.app.refresh_dirlist();
 
         loop {
-            let selected_idx = test_app.app.dir_items.state.selected().unwrap();
-            let selected_entry = &test_app.app.dir_items.items[selected_idx];
+            let selected_idx = test_app.app.pane().dir_items.state.selected().unwrap();
+            let selected_entry = &test_app.app.pane().dir_items.items[selected_idx];
             if selected_entry.file_name() == test_filename {
                 break;
             }
@@ -301,13 +1182,13 @@ mod tests {
         let _test_file = fs::File::create(&test_filepath).unwrap();
 
         let mut test_app = setup();
-        test_app.app.current_dir = test_dirpath.to_path_buf();
-        test_app.app.parent_dir = test_dirpath.parent().unwrap().to_path_buf();
+        test_app.app.pane_mut().current_dir = test_dirpath.to_path_buf();
+        test_app.app.pane_mut().parent_dir = test_dirpath.parent().unwrap().to_path_buf();
         test_app.app.refresh_dirlist();
 
         loop {
-            let selected_idx = test_app.app.dir_items.state.selected().unwrap();
-            let selected_entry = &test_app.app.dir_items.items[selected_idx];
+            let selected_idx = test_app.app.pane().dir_items.state.selected().unwrap();
+            let selected_entry = &test_app.app.pane().dir_items.items[selected_idx];
             if selected_entry.file_name() == test_filename {
                 break;
             }
@@ -331,13 +1212,13 @@ mod tests {
         let _test_file = fs::File::create(&test_filepath).unwrap();
 
         let mut test_app = setup();
-        test_app.app.current_dir = test_dirpath.to_path_buf();
-        test_app.app.parent_dir = test_dirpath.parent().unwrap().to_path_buf();
+        test_app.app.pane_mut().current_dir = test_dirpath.to_path_buf();
+        test_app.app.pane_mut().parent_dir = test_dirpath.parent().unwrap().to_path_buf();
         test_app.app.refresh_dirlist();
 
         loop {
-            let selected_idx = test_app.app.dir_items.state.selected().unwrap();
-            let selected_entry = &test_app.app.dir_items.items[selected_idx];
+            let selected_idx = test_app.app.pane().dir_items.state.selected().unwrap();
+            let selected_entry = &test_app.app.pane().dir_items.items[selected_idx];
             if selected_entry.file_name() == test_filename {
                 break;
             }
@@ -355,9 +1236,11 @@ mod tests {
 
         // turning on hidden directories, in order to find the temp directory as its hidden by default
         test_app.app.handle_keypress(KeyCode::Char('h').into());
+        // moving up selects the directory just left, so scan from the top of the listing
+        test_app.app.pane_mut().dir_items.state.select_first();
         loop {
-            let selected_idx = test_app.app.dir_items.state.selected().unwrap();
-            let selected_entry = &test_app.app.dir_items.items[selected_idx];
+            let selected_idx = test_app.app.pane().dir_items.state.selected().unwrap();
+            let selected_entry = &test_app.app.pane().dir_items.items[selected_idx];
             if selected_entry.file_name() == dest_dirpath.file_name().unwrap() {
                 test_app.app.handle_keypress(KeyCode::Right.into());
                 break;
@@ -375,7 +1258,7 @@ mod tests {
     fn test_keypress_up() {
         let mut test_app = setup();
         test_app.app.handle_keypress(KeyCode::Up.into());
-        let result = test_app.app.dir_items.state.selected();
+        let result = test_app.app.pane().dir_items.state.selected();
 
         assert_ne!(result, None);
     }
@@ -384,7 +1267,7 @@ mod tests {
     fn test_keypress_down() {
         let mut test_app = setup();
         test_app.app.handle_keypress(KeyCode::Down.into());
-        let result = test_app.app.dir_items.state.selected();
+        let result = test_app.app.pane().dir_items.state.selected();
 
         assert_ne!(result, None);
     }
@@ -393,7 +1276,7 @@ mod tests {
     fn test_keypress_esc() {
         let mut test_app = setup();
         test_app.app.handle_keypress(KeyCode::Esc.into());
-        let result = &test_app.app.current_dir;
+        let result = &test_app.app.pane().current_dir;
         let expected = fs::canonicalize("../").unwrap();
 
         assert_eq!(result, &expected);
@@ -403,9 +1286,624 @@ mod tests {
     fn test_keypress_backspace() {
         let mut test_app = setup();
         test_app.app.handle_keypress(KeyCode::Backspace.into());
-        let result = &test_app.app.current_dir;
+        let result = &test_app.app.pane().current_dir;
         let expected = fs::canonicalize("../").unwrap();
 
         assert_eq!(result, &expected);
     }
+
+    #[test]
+    fn test_keypress_tab_single_layout() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+
+        assert_eq!(test_app.app.tab().active_pane, 0);
+    }
+
+    #[test]
+    fn test_keypress_tab_dual_layout() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Char('v').into());
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        assert_eq!(test_app.app.tab().active_pane, 1);
+
+        test_app.app.handle_keypress(KeyCode::BackTab.into());
+        assert_eq!(test_app.app.tab().active_pane, 0);
+    }
+
+    #[test]
+    fn test_independent_panes() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Char('v').into());
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        test_app.app.handle_keypress(KeyCode::Esc.into());
+        test_app.app.handle_keypress(KeyCode::Char('h').into());
+
+        let expected = fs::canonicalize("../").unwrap();
+
+        assert_eq!(test_app.app.tab().panes[1].current_dir, expected);
+        assert!(test_app.app.tab().panes[1].show_hidden);
+        assert_ne!(test_app.app.tab().panes[0].current_dir, expected);
+        assert!(!test_app.app.tab().panes[0].show_hidden);
+    }
+
+    #[test]
+    fn test_keypress_v_cycles_layout() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Char('v').into());
+        assert_eq!(test_app.app.pane_layout, PaneLayout::Dual);
+
+        test_app.app.handle_keypress(KeyCode::Char('v').into());
+        assert_eq!(test_app.app.pane_layout, PaneLayout::Miller);
+
+        test_app.app.handle_keypress(KeyCode::Char('v').into());
+        assert_eq!(test_app.app.pane_layout, PaneLayout::Single);
+    }
+
+    #[test]
+    fn test_keypress_i_toggles_preview() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Char('i').into());
+        assert!(test_app.app.show_preview);
+
+        test_app.app.handle_keypress(KeyCode::Char('i').into());
+        assert!(!test_app.app.show_preview);
+    }
+
+    #[test]
+    fn test_keypress_filter() {
+        let test_dir = tempdir().unwrap();
+        let _first_file = fs::File::create(test_dir.path().join("filter_match.txt")).unwrap();
+        let _second_file = fs::File::create(test_dir.path().join("other.txt")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.pane_mut().enter_dir(test_dir.path());
+
+        test_app.app.handle_keypress(KeyCode::Char('/').into());
+        assert_eq!(test_app.app.app_state, AppState::Filtering);
+        for c in "fmt".chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        assert_eq!(test_app.app.pane().dir_items.items.len(), 1);
+        assert_eq!(test_app.app.status_text, "/fmt");
+
+        test_app.app.handle_keypress(KeyCode::Esc.into());
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert_eq!(test_app.app.pane().dir_items.items.len(), 2);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_filter_accept() {
+        let test_dir = tempdir().unwrap();
+        let _first_file = fs::File::create(test_dir.path().join("filter_match.txt")).unwrap();
+        let _second_file = fs::File::create(test_dir.path().join("other.txt")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.pane_mut().enter_dir(test_dir.path());
+
+        test_app.app.handle_keypress(KeyCode::Char('/').into());
+        test_app.app.handle_keypress(KeyCode::Char('o').into());
+        test_app.app.handle_keypress(KeyCode::Char('t').into());
+        test_app.app.handle_keypress(KeyCode::Backspace.into());
+        test_app.app.handle_keypress(KeyCode::Char('h').into());
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert_eq!(test_app.app.pane().filter, "oh");
+        assert_eq!(test_app.app.pane().dir_items.items.len(), 1);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_finder() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _first_file = fs::File::create(test_subdir.join("find_me.txt")).unwrap();
+        let _second_file = fs::File::create(test_subdir.join("other.txt")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.pane_mut().enter_dir(test_dir.path());
+
+        test_app.app.handle_keypress(KeyCode::Char('f').into());
+        assert_eq!(test_app.app.app_state, AppState::Finding);
+        while test_app.app.finder.as_ref().unwrap().is_busy() {
+            test_app.app.tick();
+        }
+        for c in "findme".chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert!(test_app.app.finder.is_none());
+        assert_eq!(test_app.app.pane().current_dir, test_subdir);
+        assert_eq!(
+            test_app.app.pane().selected_entry().unwrap().file_name(),
+            "find_me.txt"
+        );
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_content_search() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let test_filepath = test_subdir.join("search_me.txt");
+        fs::write(&test_filepath, "first line\nneedle\n").unwrap();
+
+        let mut test_app = setup();
+        test_app.app.pane_mut().enter_dir(test_dir.path());
+
+        test_app.app.handle_keypress(KeyCode::Char('F').into());
+        assert_eq!(test_app.app.app_state, AppState::Searching);
+        for c in "needle".chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+        while test_app.app.content_search.as_ref().unwrap().is_searching {
+            test_app.app.tick();
+        }
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert!(test_app.app.content_search.is_none());
+        assert!(test_app.app.show_preview);
+        assert_eq!(test_app.app.pane().current_dir, test_subdir);
+        assert_eq!(test_app.app.pane().preview.path, Some(test_filepath));
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_path_prompt() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir_all(test_subdir.join("nested")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.pane_mut().enter_dir(test_dir.path());
+
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        test_app.app.handle_keypress(KeyCode::Char('d').into());
+        assert_eq!(test_app.app.app_state, AppState::EnteringPath);
+        for c in "su".chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        assert_eq!(test_app.app.prompt_input, "subdir/");
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        assert_eq!(test_app.app.prompt_input, "subdir/nested/");
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert_eq!(test_app.app.pane().current_dir, test_subdir.join("nested"));
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_path_prompt_invalid() {
+        let mut test_app = setup();
+        let init_dir = test_app.app.pane().current_dir.clone();
+
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        test_app.app.handle_keypress(KeyCode::Char('d').into());
+        for c in "fake_dir".chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+
+        assert_eq!(test_app.app.pane().current_dir, init_dir);
+        assert!(test_app.app.status_text.starts_with("Unable to go to path"));
+    }
+
+    #[test]
+    fn test_keypress_bookmarks() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.pane_mut().enter_dir(&test_subdir);
+
+        test_app.app.handle_keypress(KeyCode::Char('m').into());
+        assert_eq!(test_app.app.app_state, AppState::NamingBookmark);
+        test_app.app.handle_keypress(KeyCode::Char('a').into());
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+        assert_eq!(test_app.app.bookmarks.entries.len(), 1);
+
+        test_app.app.pane_mut().enter_dir(test_dir.path());
+        test_app.app.handle_keypress(KeyCode::Char('\'').into());
+        assert_eq!(test_app.app.app_state, AppState::Bookmarks);
+        test_app.app.handle_keypress(KeyCode::Char('a').into());
+
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert_eq!(test_app.app.pane().current_dir, test_subdir);
+        assert_eq!(test_app.app.pane().parent_dir, test_dir.path());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_bookmarks_delete() {
+        let mut test_app = setup();
+        test_app.app.bookmarks.add("a", "/tmp").unwrap();
+
+        test_app.app.handle_keypress(KeyCode::Char('\'').into());
+        test_app.app.handle_keypress(KeyCode::Delete.into());
+        test_app.app.handle_keypress(KeyCode::Esc.into());
+
+        assert!(test_app.app.bookmarks.entries.is_empty());
+        assert_eq!(test_app.app.app_state, AppState::Running);
+    }
+
+    #[test]
+    fn test_keypress_jump() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("jump_target");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.enter_dir(test_dir.path());
+        test_app.app.handle_keypress(KeyCode::Right.into());
+        test_app.app.handle_keypress(KeyCode::Left.into());
+        assert_eq!(test_app.app.frecency.entries.len(), 2);
+
+        test_app.app.handle_keypress(KeyCode::Char('z').into());
+        assert_eq!(test_app.app.app_state, AppState::Jumping);
+        for c in "jmptgt".chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert_eq!(test_app.app.pane().current_dir, test_subdir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_history() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let mut test_app = setup();
+        let init_dir = test_app.app.pane().current_dir.clone();
+        test_app.app.go_to_path(test_subdir.to_str().unwrap());
+
+        test_app.app.handle_keypress(KeyCode::Char('[').into());
+        assert_eq!(test_app.app.pane().current_dir, init_dir);
+
+        test_app.app.handle_keypress(KeyCode::Char(']').into());
+        assert_eq!(test_app.app.pane().current_dir, test_subdir);
+
+        test_app
+            .app
+            .handle_keypress(KeyEvent::new(KeyCode::Left, KeyModifiers::ALT));
+        assert_eq!(test_app.app.pane().current_dir, init_dir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_tabs() {
+        let test_dir = tempdir().unwrap();
+        let mut test_app = setup();
+        let init_dir = test_app.app.pane().current_dir.clone();
+
+        test_app.app.handle_keypress(KeyCode::Char('t').into());
+        assert_eq!(test_app.app.tabs.len(), 2);
+        assert_eq!(test_app.app.active_tab, 1);
+        assert_eq!(test_app.app.pane().current_dir, init_dir);
+
+        test_app.app.go_to_path(test_dir.path().to_str().unwrap());
+        test_app.app.handle_keypress(KeyCode::Char('>').into());
+        assert_eq!(test_app.app.active_tab, 0);
+        assert_eq!(test_app.app.pane().current_dir, init_dir);
+
+        test_app.app.handle_keypress(KeyCode::Char('<').into());
+        assert_eq!(test_app.app.pane().current_dir, test_dir.path());
+
+        test_app.app.handle_keypress(KeyCode::Char('w').into());
+        assert_eq!(test_app.app.tabs.len(), 1);
+        assert_eq!(test_app.app.active_tab, 0);
+        assert_eq!(test_app.app.pane().current_dir, init_dir);
+
+        test_app.app.handle_keypress(KeyCode::Char('w').into());
+        assert_eq!(test_app.app.tabs.len(), 1);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_open_tab_copies_both_panes() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Char('v').into());
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        test_app.app.pane_mut().toggle_hidden();
+        test_app.app.pane_mut().toggle_long_listing();
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+
+        test_app.app.handle_keypress(KeyCode::Char('t').into());
+        assert_eq!(test_app.app.active_tab, 1);
+        assert_eq!(test_app.app.tab().active_pane, 0);
+        assert!(!test_app.app.pane().show_hidden);
+
+        // the inactive pane matches the inactive pane of the tab it was opened from
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        assert!(test_app.app.pane().show_hidden);
+        assert!(test_app.app.pane().long_listing);
+    }
+
+    #[test]
+    fn test_keypress_tree_paste() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_subdir.join("nested.txt")).unwrap();
+        let test_filepath = test_dir.path().join("top.txt");
+        let _other_file = fs::File::create(&test_filepath).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.go_to_path(test_dir.path().to_str().unwrap());
+        test_app.app.handle_keypress(KeyCode::Char('T').into());
+        test_app.app.pane_mut().select_path(&test_subdir);
+        test_app.app.handle_keypress(KeyCode::Char('e').into());
+        assert_eq!(test_app.app.pane().dir_items.items.len(), 3);
+
+        // pasting with a nested entry selected puts the copy next to it
+        test_app.app.pane_mut().select_path(&test_filepath);
+        test_app.app.handle_keypress(KeyCode::Char('c').into());
+        test_app
+            .app
+            .pane_mut()
+            .select_path(test_subdir.join("nested.txt"));
+        test_app.app.handle_keypress(KeyCode::Char('p').into());
+        assert!(test_subdir.join("top.txt").exists());
+        assert_eq!(test_app.app.pane().dir_items.items.len(), 4);
+        test_dir.close().unwrap();
+    }
+
+    fn mouse_event(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_mouse_click_and_double_click() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("b_subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("a_file.txt")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.go_to_path(test_dir.path().to_str().unwrap());
+        test_app.app.click_areas.panes[0] = Some(Rect::new(0, 1, 40, 10));
+
+        let click = mouse_event(MouseEventKind::Down(MouseButton::Left), 5, 3);
+        test_app.app.handle_mouse(click);
+        assert_eq!(
+            test_app.app.pane().selected_entry().unwrap().path(),
+            test_subdir
+        );
+
+        test_app.app.handle_mouse(click);
+        assert_eq!(test_app.app.pane().current_dir, test_subdir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_mouse_scroll_and_title_click() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_subdir.join("a_file.txt")).unwrap();
+        let _other_file = fs::File::create(test_subdir.join("b_file.txt")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.go_to_path(test_subdir.to_str().unwrap());
+        test_app.app.click_areas.panes[0] = Some(Rect::new(0, 1, 40, 10));
+        test_app
+            .app
+            .handle_mouse(mouse_event(MouseEventKind::ScrollDown, 5, 5));
+        assert_eq!(
+            test_app.app.pane().selected_entry().unwrap().file_name(),
+            "b_file.txt"
+        );
+
+        // clicking anywhere in the second to last segment of the path goes to the parent
+        test_app.app.click_areas.title_path = Rect::new(0, 0, 80, 1);
+        let parent_column = test_dir.path().to_string_lossy().chars().count() - 1;
+        test_app.app.handle_mouse(mouse_event(
+            MouseEventKind::Down(MouseButton::Left),
+            parent_column as u16,
+            0,
+        ));
+        assert_eq!(test_app.app.pane().current_dir, test_dir.path());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_keypress_motions_with_count() {
+        let test_dir = tempdir().unwrap();
+        for idx in 0..10 {
+            let _test_file = fs::File::create(test_dir.path().join(format!("{idx}.txt"))).unwrap();
+        }
+
+        let mut test_app = setup();
+        test_app.app.go_to_path(test_dir.path().to_str().unwrap());
+        test_app.app.pane_mut().page_height = 4;
+
+        test_app.app.handle_keypress(KeyCode::Char('5').into());
+        assert_eq!(test_app.app.status_text, "5");
+        test_app.app.handle_keypress(KeyCode::Char('j').into());
+        assert_eq!(test_app.app.pane().dir_items.state.selected(), Some(5));
+
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        assert_eq!(test_app.app.pane().dir_items.state.selected(), Some(0));
+
+        test_app
+            .app
+            .handle_keypress(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT));
+        assert_eq!(test_app.app.pane().dir_items.state.selected(), Some(9));
+
+        test_app
+            .app
+            .handle_keypress(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(test_app.app.pane().dir_items.state.selected(), Some(7));
+
+        test_app.app.handle_keypress(KeyCode::Char('3').into());
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        assert_eq!(test_app.app.pane().dir_items.state.selected(), Some(2));
+
+        test_app.app.handle_keypress(KeyCode::PageDown.into());
+        assert_eq!(test_app.app.pane().dir_items.state.selected(), Some(6));
+
+        // an unbound sequence is dropped along with its count
+        test_app.app.handle_keypress(KeyCode::Char('2').into());
+        test_app.app.handle_keypress(KeyCode::Char('g').into());
+        test_app.app.handle_keypress(KeyCode::Char('q').into());
+        assert_eq!(test_app.app.app_state, AppState::Running);
+        assert!(test_app.app.pending_keys.is_empty());
+        assert_eq!(test_app.app.pending_count, None);
+        test_dir.close().unwrap();
+    }
+
+    fn type_command(test_app: &mut TestContext, input: &str) {
+        test_app.app.handle_keypress(KeyCode::Char(':').into());
+        for c in input.chars() {
+            test_app.app.handle_keypress(KeyCode::Char(c).into());
+        }
+        test_app.app.handle_keypress(KeyCode::Enter.into());
+    }
+
+    #[test]
+    fn test_command_line_file_commands() {
+        let test_dir = tempdir().unwrap();
+        let mut test_app = setup();
+        test_app.app.go_to_path(test_dir.path().to_str().unwrap());
+
+        type_command(&mut test_app, "mkdir 'new dir'");
+        assert!(test_dir.path().join("new dir").is_dir());
+        assert_eq!(test_app.app.app_state, AppState::Running);
+
+        type_command(&mut test_app, "touch a.txt b.txt");
+        assert!(test_dir.path().join("a.txt").is_file());
+        assert_eq!(
+            test_app.app.pane().selected_entry().unwrap().file_name(),
+            "b.txt"
+        );
+
+        type_command(&mut test_app, "rename c.txt");
+        assert!(!test_dir.path().join("b.txt").exists());
+        assert_eq!(
+            test_app.app.pane().selected_entry().unwrap().file_name(),
+            "c.txt"
+        );
+
+        type_command(&mut test_app, "rename a.txt");
+        assert!(test_app.app.status_text.starts_with("Unable to rename"));
+
+        type_command(&mut test_app, r"cd new\ dir");
+        assert_eq!(
+            test_app.app.pane().current_dir,
+            test_dir.path().join("new dir")
+        );
+
+        type_command(&mut test_app, "hidden on");
+        assert!(test_app.app.pane().show_hidden);
+        type_command(&mut test_app, "columns name inode");
+        assert!(test_app.app.pane().long_listing);
+        assert_eq!(
+            test_app.app.pane().columns,
+            vec![Column::Name, Column::Inode]
+        );
+        type_command(&mut test_app, "theme high-contrast");
+        assert_eq!(test_app.app.theme, Theme::high_contrast());
+        type_command(&mut test_app, "theme sepia");
+        assert_eq!(test_app.app.theme, Theme::high_contrast());
+        type_command(&mut test_app, "explode");
+        assert!(test_app.app.status_text.starts_with("Unknown command"));
+
+        type_command(&mut test_app, "q");
+        assert_eq!(test_app.app.app_state, AppState::Exit);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_command_line_history_and_completion() {
+        let mut test_app = setup();
+        type_command(&mut test_app, "hidden");
+        type_command(&mut test_app, "cursor_bottom");
+
+        test_app.app.handle_keypress(KeyCode::Char(':').into());
+        test_app.app.handle_keypress(KeyCode::Up.into());
+        assert_eq!(test_app.app.prompt_input, "cursor_bottom");
+        test_app.app.handle_keypress(KeyCode::Up.into());
+        assert_eq!(test_app.app.prompt_input, "hidden");
+        test_app.app.handle_keypress(KeyCode::Down.into());
+        test_app.app.handle_keypress(KeyCode::Down.into());
+        assert_eq!(test_app.app.prompt_input, "");
+
+        test_app.app.handle_keypress(KeyCode::Char('m').into());
+        test_app.app.handle_keypress(KeyCode::Char('k').into());
+        test_app.app.handle_keypress(KeyCode::Tab.into());
+        assert_eq!(test_app.app.prompt_input, "mkdir");
+        assert_eq!(test_app.app.status_text, ":mkdir");
+        test_app.app.handle_keypress(KeyCode::Esc.into());
+        assert_eq!(test_app.app.app_state, AppState::Running);
+    }
+
+    #[test]
+    fn test_keypress_help() {
+        let mut test_app = setup();
+        test_app.app.handle_keypress(KeyCode::Char('?').into());
+        assert_eq!(test_app.app.app_state, AppState::Help);
+
+        test_app.app.handle_keypress(KeyCode::Char('j').into());
+        assert_eq!(test_app.app.help_state.selected(), Some(1));
+
+        test_app.app.handle_keypress(KeyCode::Esc.into());
+        assert_eq!(test_app.app.app_state, AppState::Running);
+    }
+
+    #[test]
+    fn test_keypress_sort() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("a_small.txt"), "0").unwrap();
+        // big enough to be larger than the directory itself
+        fs::write(test_dir.path().join("b_big.txt"), [0; 10000]).unwrap();
+        fs::create_dir(test_dir.path().join("c_dir")).unwrap();
+
+        let mut test_app = setup();
+        test_app.app.go_to_path(test_dir.path().to_str().unwrap());
+        let first_name = |test_app: &TestContext| {
+            test_app
+                .app
+                .pane()
+                .dir_items
+                .items
+                .first()
+                .unwrap()
+                .file_name()
+        };
+
+        type_command(&mut test_app, "sort size");
+        assert_eq!(first_name(&test_app), "b_big.txt");
+
+        test_app.app.handle_keypress(KeyCode::Char('r').into());
+        assert!(test_app.app.pane().sort_order.reverse);
+        test_app
+            .app
+            .handle_keypress(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT));
+        assert_eq!(first_name(&test_app), "c_dir");
+
+        test_app.app.handle_keypress(KeyCode::Char('s').into());
+        assert_eq!(test_app.app.pane().sort_order.mode, SortMode::Modified);
+        test_dir.close().unwrap();
+    }
 }
@@ -0,0 +1,206 @@
+use std::{
+    ffi::OsStr,
+    fs,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+};
+
+use log::{debug, error};
+use ratatui::widgets::ListState;
+
+use crate::path;
+
+const BOOKMARKS_FILENAME: &str = "bookmarks";
+
+pub struct Bookmark {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+// bookmarks are kept in a file in the data directory, one `name<TAB>path` per line with the
+// path's raw bytes so non UTF-8 paths come back unchanged. That leaves tabs and newlines out of
+// names and newlines out of paths.
+pub struct Bookmarks {
+    pub entries: Vec<Bookmark>,
+    pub state: ListState,
+    filepath: Option<PathBuf>,
+}
+
+impl Bookmarks {
+    pub fn load_default() -> Self {
+        Bookmarks::load(path::get_data_dir().map(|x| x.join(BOOKMARKS_FILENAME)))
+    }
+
+    pub fn load(filepath: Option<PathBuf>) -> Self {
+        let mut entries = Vec::new();
+        if let Some(contents) = filepath.as_ref().and_then(|x| fs::read(x).ok()) {
+            for line in contents.split(|x| *x == b'\n').filter(|x| !x.is_empty()) {
+                match parse_bookmark(line) {
+                    Some(bookmark) => entries.push(bookmark),
+                    None => debug!(
+                        "Skipping malformed bookmark: {:?}",
+                        String::from_utf8_lossy(line)
+                    ),
+                }
+            }
+        }
+
+        Bookmarks {
+            entries,
+            state: ListState::default(),
+            filepath,
+        }
+    }
+
+    // adding a bookmark under an existing name replaces it
+    pub fn add<T: AsRef<Path>>(&mut self, name: &str, bookmark_path: T) -> Result<(), String> {
+        if name.contains(['\t', '\n']) {
+            return Err(format!(
+                "Bookmark names can't contain tabs or newlines, {name:?}"
+            ));
+        }
+        if bookmark_path
+            .as_ref()
+            .as_os_str()
+            .as_bytes()
+            .contains(&b'\n')
+        {
+            return Err(format!(
+                "Unable to bookmark {:?}, its path contains a newline",
+                bookmark_path.as_ref()
+            ));
+        }
+
+        let bookmark = Bookmark {
+            name: name.to_string(),
+            path: bookmark_path.as_ref().to_path_buf(),
+        };
+        match self.entries.iter_mut().find(|x| x.name == name) {
+            Some(existing) => *existing = bookmark,
+            None => self.entries.push(bookmark),
+        }
+        self.save();
+        Ok(())
+    }
+
+    pub fn remove_selected(&mut self) {
+        if let Some(idx) = self.state.selected() {
+            if idx < self.entries.len() {
+                self.entries.remove(idx);
+                self.save();
+            }
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Bookmark> {
+        self.entries.iter().find(|x| x.name == name)
+    }
+
+    pub fn selected(&self) -> Option<&Bookmark> {
+        self.state.selected().and_then(|idx| self.entries.get(idx))
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        self.state.select_previous();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        self.state.select_next();
+    }
+
+    fn save(&self) {
+        let Some(filepath) = &self.filepath else {
+            return;
+        };
+
+        let mut contents = Vec::new();
+        for bookmark in &self.entries {
+            contents.extend_from_slice(format!("{}\t", bookmark.name).as_bytes());
+            contents.extend_from_slice(bookmark.path.as_os_str().as_bytes());
+            contents.push(b'\n');
+        }
+        let save_result = fs::create_dir_all(path::get_parent_dir(filepath))
+            .and_then(|_| fs::write(filepath, contents));
+        if let Err(e) = save_result {
+            error!("Unable to save bookmarks: {e:?}");
+        }
+    }
+}
+
+fn parse_bookmark(line: &[u8]) -> Option<Bookmark> {
+    let tab_idx = line.iter().position(|x| *x == b'\t')?;
+    let name = str::from_utf8(&line[..tab_idx]).ok()?;
+    Some(Bookmark {
+        name: name.to_string(),
+        path: PathBuf::from(OsStr::from_bytes(&line[tab_idx + 1..])),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_bookmarks_persist() {
+        let test_dir = tempdir().unwrap();
+        let bookmarks_filepath = test_dir.path().join("data").join(BOOKMARKS_FILENAME);
+
+        let mut bookmarks = Bookmarks::load(Some(bookmarks_filepath.clone()));
+        bookmarks.add("a", "/tmp").unwrap();
+        bookmarks.add("home", "/home").unwrap();
+
+        let bookmarks = Bookmarks::load(Some(bookmarks_filepath));
+        assert_eq!(bookmarks.entries.len(), 2);
+        assert_eq!(bookmarks.find("home").unwrap().path, PathBuf::from("/home"));
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_bookmarks_replace() {
+        let test_dir = tempdir().unwrap();
+        let bookmarks_filepath = test_dir.path().join(BOOKMARKS_FILENAME);
+
+        let mut bookmarks = Bookmarks::load(Some(bookmarks_filepath));
+        bookmarks.add("a", "/tmp").unwrap();
+        bookmarks.add("a", "/home").unwrap();
+
+        assert_eq!(bookmarks.entries.len(), 1);
+        assert_eq!(bookmarks.find("a").unwrap().path, PathBuf::from("/home"));
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_bookmarks_remove() {
+        let test_dir = tempdir().unwrap();
+        let bookmarks_filepath = test_dir.path().join(BOOKMARKS_FILENAME);
+
+        let mut bookmarks = Bookmarks::load(Some(bookmarks_filepath.clone()));
+        bookmarks.add("a", "/tmp").unwrap();
+        bookmarks.add("b", "/home").unwrap();
+        bookmarks.state.select(Some(0));
+        bookmarks.remove_selected();
+
+        let bookmarks = Bookmarks::load(Some(bookmarks_filepath));
+        assert_eq!(bookmarks.entries.len(), 1);
+        assert!(bookmarks.find("a").is_none());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_bookmarks_special_characters() {
+        let test_dir = tempdir().unwrap();
+        let bookmarks_filepath = test_dir.path().join(BOOKMARKS_FILENAME);
+        let non_utf8_dir = PathBuf::from(OsStr::from_bytes(b"/srv/caf\xe9\tbar"));
+
+        let mut bookmarks = Bookmarks::load(Some(bookmarks_filepath.clone()));
+        bookmarks.add("a", &non_utf8_dir).unwrap();
+        assert!(bookmarks.add("b\tc", "/tmp").is_err());
+        assert!(bookmarks.add("d", "/srv/two\nlines").is_err());
+
+        let bookmarks = Bookmarks::load(Some(bookmarks_filepath));
+        assert_eq!(bookmarks.entries.len(), 1);
+        assert_eq!(bookmarks.find("a").unwrap().path, non_utf8_dir);
+        test_dir.close().unwrap();
+    }
+}
@@ -19,7 +19,7 @@ pub fn parse_args(args: Vec<String>) -> PathBuf {
                 // attempt to open the directory passed in as an argument
                 // failing that, open the home directory based on the user env
                 // if all fails, just start in the current directory
-                init_dir = match std::env::home_dir() {
+                init_dir = match path::get_home_dir() {
                     Some(x) => x,
                     None => path::get_current_dirpath(),
                 };
@@ -74,7 +74,7 @@ mod tests {
     #[test]
     fn test_invalid_dir() {
         let args: Vec<String> = vec!["program".to_string(), "fake_dir".to_string()];
-        let expected_path = std::env::home_dir().unwrap();
+        let expected_path = path::get_home_dir().unwrap();
 
         let result = parse_args(args);
 
@@ -0,0 +1,278 @@
+use std::{
+    fs::{DirEntry, Metadata},
+    io,
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Local};
+use human_bytes::human_bytes;
+use users::{Groups, Users, UsersCache};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+pub const COLUMN_SPACING: u16 = 2;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Column {
+    Name,
+    Size,
+    Modified,
+    Accessed,
+    Changed,
+    Permissions,
+    Owner,
+    Group,
+    Inode,
+    Links,
+    Extension,
+    Mime,
+}
+
+// the names columns go by in the config and on the command line
+const COLUMN_NAMES: [(Column, &str); 12] = [
+    (Column::Name, "name"),
+    (Column::Size, "size"),
+    (Column::Modified, "mtime"),
+    (Column::Accessed, "atime"),
+    (Column::Changed, "ctime"),
+    (Column::Permissions, "perms"),
+    (Column::Owner, "owner"),
+    (Column::Group, "group"),
+    (Column::Inode, "inode"),
+    (Column::Links, "links"),
+    (Column::Extension, "extension"),
+    (Column::Mime, "mime"),
+];
+
+// the same columns as `ls -l`
+pub const LONG_LISTING_COLUMNS: [Column; 6] = [
+    Column::Permissions,
+    Column::Owner,
+    Column::Group,
+    Column::Size,
+    Column::Modified,
+    Column::Name,
+];
+
+impl Column {
+    pub fn from_name(name: &str) -> Option<Column> {
+        COLUMN_NAMES
+            .iter()
+            .find(|(_, column_name)| *column_name == name)
+            .map(|(column, _)| *column)
+    }
+
+    pub fn is_right_aligned(&self) -> bool {
+        matches!(self, Column::Size | Column::Inode | Column::Links)
+    }
+}
+
+// a column set has to include the name, otherwise there'd be no telling the entries apart
+pub fn parse_columns<T: AsRef<str>>(column_names: &[T]) -> Result<Vec<Column>, String> {
+    let columns = column_names
+        .iter()
+        .map(|x| {
+            Column::from_name(x.as_ref()).ok_or_else(|| format!("Unknown column {:?}", x.as_ref()))
+        })
+        .collect::<Result<Vec<Column>, String>>()?;
+    if !columns.contains(&Column::Name) {
+        return Err(String::from("The columns must include name"));
+    }
+    Ok(columns)
+}
+
+// the text of every column for every entry, worked out once when the listing is loaded rather
+// than on every draw. The name is left empty as it's drawn from the entry itself.
+pub fn detail_cells(items: &[DirEntry], columns: &[Column]) -> Vec<Vec<String>> {
+    // owner and group names would otherwise be looked up again for every entry
+    let users_cache = UsersCache::new();
+    items
+        .iter()
+        .map(|x| {
+            let metadata = x.metadata().ok();
+            columns
+                .iter()
+                .map(|column| match (column, &metadata) {
+                    (Column::Extension | Column::Mime, _) => entry_cell(column, x),
+                    (_, Some(metadata)) => detail_cell(column, metadata, &users_cache),
+                    (_, None) => String::new(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// the columns that only need the entry's path
+fn entry_cell(column: &Column, entry: &DirEntry) -> String {
+    let entry_path = entry.path();
+    match column {
+        Column::Extension if !entry_path.is_dir() => entry_path
+            .extension()
+            .map(|x| x.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        Column::Mime if entry_path.is_dir() => String::from("inode/directory"),
+        Column::Mime => mime_guess::from_path(&entry_path)
+            .first_raw()
+            .unwrap_or_default()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn detail_cell(column: &Column, metadata: &Metadata, users_cache: &UsersCache) -> String {
+    match column {
+        Column::Size => format_size(metadata),
+        Column::Modified => format_time(metadata.modified()),
+        Column::Accessed => format_time(metadata.accessed()),
+        // std only has the creation time, the status change time comes from the raw stat
+        Column::Changed => DateTime::from_timestamp(metadata.ctime(), metadata.ctime_nsec() as u32)
+            .map(|x| x.with_timezone(&Local).format(DATE_FORMAT).to_string())
+            .unwrap_or_default(),
+        Column::Permissions => format_permissions(metadata),
+        Column::Owner => format_owner(metadata, users_cache),
+        Column::Group => format_group(metadata, users_cache),
+        Column::Inode => metadata.ino().to_string(),
+        Column::Links => metadata.nlink().to_string(),
+        Column::Name | Column::Extension | Column::Mime => String::new(),
+    }
+}
+
+// the fields the status bar shares with the long listing, so both show them the same way
+pub fn format_size(metadata: &Metadata) -> String {
+    human_bytes(metadata.len() as f64)
+}
+
+pub fn format_permissions(metadata: &Metadata) -> String {
+    unix_mode::to_string(metadata.permissions().mode())
+}
+
+// the uid when there's no user by that id
+pub fn format_owner(metadata: &Metadata, users: &impl Users) -> String {
+    match users.get_user_by_uid(metadata.uid()) {
+        Some(user) => user.name().to_string_lossy().to_string(),
+        None => metadata.uid().to_string(),
+    }
+}
+
+pub fn format_group(metadata: &Metadata, groups: &impl Groups) -> String {
+    match groups.get_group_by_gid(metadata.gid()) {
+        Some(group) => group.name().to_string_lossy().to_string(),
+        None => metadata.gid().to_string(),
+    }
+}
+
+fn format_time(time: io::Result<SystemTime>) -> String {
+    time.map(|x| DateTime::<Local>::from(x).format(DATE_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+// gives every column the width it wants while they fit. When they don't, the columns after the
+// name are dropped from the right, then the ones before it, and only then is the name truncated.
+// A width of 0 means the column is left out.
+pub fn fit_column_widths(columns: &[Column], wanted_widths: &[u16], available: u16) -> Vec<u16> {
+    let mut widths = wanted_widths.to_vec();
+    let total_width = |widths: &[u16]| -> u16 {
+        let shown = widths.iter().filter(|x| **x > 0).count() as u16;
+        widths.iter().sum::<u16>() + COLUMN_SPACING * shown.saturating_sub(1)
+    };
+
+    let Some(name_idx) = columns.iter().position(|x| *x == Column::Name) else {
+        return widths;
+    };
+    let drop_order = (name_idx + 1..columns.len())
+        .rev()
+        .chain((0..name_idx).rev());
+    for column_idx in drop_order {
+        if total_width(&widths) <= available {
+            break;
+        }
+        widths[column_idx] = 0;
+    }
+
+    widths[name_idx] = widths[name_idx].max(1);
+    let others_width = total_width(&widths) - widths[name_idx];
+    widths[name_idx] = available.saturating_sub(others_width).max(1);
+    widths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_detail_cells() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join("test_file.txt");
+        fs::write(&test_filepath, "0123456789").unwrap();
+        fs::set_permissions(&test_filepath, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let items = path::get_dir_items(test_dir.path(), &false);
+        let cells = detail_cells(&items, &LONG_LISTING_COLUMNS);
+
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0][0], "-rw-r--r--");
+        assert_eq!(cells[0][3], "10 B");
+        assert_eq!(cells[0][4].len(), "2000-01-01 00:00".len());
+        assert_eq!(cells[0][5], "");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_extra_detail_cells() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("test_file.txt"), "").unwrap();
+
+        let items = path::get_dir_items(test_dir.path(), &false);
+        let columns = parse_columns(&["name", "links", "extension", "mime", "ctime"]).unwrap();
+        let cells = detail_cells(&items, &columns);
+
+        assert_eq!(cells[0][1], "1");
+        assert_eq!(cells[0][2], "txt");
+        assert_eq!(cells[0][3], "text/plain");
+        assert_eq!(cells[0][4].len(), "2000-01-01 00:00".len());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(
+            parse_columns(&["size", "name"]).unwrap(),
+            vec![Column::Size, Column::Name]
+        );
+        assert!(parse_columns(&["size"]).is_err());
+        assert!(parse_columns(&["name", "colour"]).is_err());
+    }
+
+    #[test]
+    fn test_fit_column_widths() {
+        let columns = [
+            Column::Permissions,
+            Column::Name,
+            Column::Size,
+            Column::Modified,
+        ];
+        let wanted_widths = [10, 20, 6, 16];
+
+        // everything fits, the name takes up the rest
+        assert_eq!(
+            fit_column_widths(&columns, &wanted_widths, 80),
+            vec![10, 42, 6, 16]
+        );
+        // the last column goes first, then the one before it
+        assert_eq!(
+            fit_column_widths(&columns, &wanted_widths, 50),
+            vec![10, 30, 6, 0]
+        );
+        assert_eq!(
+            fit_column_widths(&columns, &wanted_widths, 25),
+            vec![0, 25, 0, 0]
+        );
+        assert_eq!(
+            fit_column_widths(&columns, &wanted_widths, 8),
+            vec![0, 8, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    columns::{self, Column},
+    keymap::Action,
+    sort::SortMode,
+};
+
+// what can be typed on the `:` command line, every action can also be run by its name
+#[derive(Debug, PartialEq)]
+pub enum AppCommand {
+    Action(Action),
+    Cd(String),
+    Mkdir(Vec<String>),
+    Touch(Vec<String>),
+    Rename(String),
+    Hidden(Option<bool>),
+    Sort(SortMode),
+    Columns(Vec<Column>),
+    Theme(Option<String>),
+}
+
+// the commands that aren't actions, for completion
+const COMMAND_NAMES: &[&str] = &[
+    "cd", "mkdir", "touch", "rename", "hidden", "sort", "columns", "theme", "q",
+];
+
+pub fn parse_command(input: &str) -> Result<AppCommand, String> {
+    let args = split_args(input)?;
+    let Some((command_name, args)) = args.split_first() else {
+        return Err(String::from("No command given"));
+    };
+
+    match (command_name.as_str(), args) {
+        ("cd", []) => Ok(AppCommand::Cd(String::from("~"))),
+        ("cd", [dir]) => Ok(AppCommand::Cd(dir.clone())),
+        ("mkdir", [_, ..]) => Ok(AppCommand::Mkdir(args.to_vec())),
+        ("touch", [_, ..]) => Ok(AppCommand::Touch(args.to_vec())),
+        ("rename", [new_name]) => Ok(AppCommand::Rename(new_name.clone())),
+        ("hidden", []) => Ok(AppCommand::Hidden(None)),
+        ("hidden", [value]) => match value.as_str() {
+            "on" | "true" => Ok(AppCommand::Hidden(Some(true))),
+            "off" | "false" => Ok(AppCommand::Hidden(Some(false))),
+            _ => Err(format!("Expected on or off for hidden, not {value:?}")),
+        },
+        ("sort", [mode_name]) => SortMode::from_name(mode_name)
+            .map(AppCommand::Sort)
+            .ok_or_else(|| format!("Unknown sort mode {mode_name:?}")),
+        ("columns", [_, ..]) => columns::parse_columns(args).map(AppCommand::Columns),
+        ("theme", []) => Ok(AppCommand::Theme(None)),
+        ("theme", [theme_name]) => Ok(AppCommand::Theme(Some(theme_name.clone()))),
+        ("q", []) => Ok(AppCommand::Action(Action::Quit)),
+        (command_name, []) if Action::from_name(command_name).is_some() => {
+            Ok(AppCommand::Action(Action::from_name(command_name).unwrap()))
+        }
+        (command_name, _)
+            if COMMAND_NAMES.contains(&command_name)
+                || Action::from_name(command_name).is_some() =>
+        {
+            Err(format!("Wrong number of arguments for {command_name}"))
+        }
+        (command_name, _) => Err(format!("Unknown command {command_name:?}")),
+    }
+}
+
+// splits on whitespace like a shell would, with quotes and backslashes to keep spaces in an argument
+pub fn split_args(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current_arg: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => {
+                let escaped = chars.next().ok_or("Nothing to escape at the end")?;
+                current_arg.get_or_insert_with(String::new).push(escaped);
+            }
+            (c, Some(quote_char)) if c == quote_char => quote = None,
+            (c, Some(_)) => current_arg.get_or_insert_with(String::new).push(c),
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                current_arg.get_or_insert_with(String::new);
+            }
+            (c, None) if c.is_whitespace() => args.extend(current_arg.take()),
+            (c, None) => current_arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if let Some(quote_char) = quote {
+        return Err(format!("Missing closing {quote_char}"));
+    }
+    args.extend(current_arg);
+    Ok(args)
+}
+
+pub fn complete_command_name(input: &str) -> Vec<String> {
+    let mut completions: Vec<String> = COMMAND_NAMES
+        .iter()
+        .copied()
+        .chain(Action::names())
+        .filter(|x| x.starts_with(input))
+        .map(String::from)
+        .collect();
+    completions.sort();
+    completions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args("touch  a.txt 'b c.txt' \"d'e\" f\\ g").unwrap(),
+            vec!["touch", "a.txt", "b c.txt", "d'e", "f g"]
+        );
+        assert_eq!(split_args("rename ''").unwrap(), vec!["rename", ""]);
+        assert!(split_args("cd \"unfinished").is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("cd ~/projects").unwrap(),
+            AppCommand::Cd(String::from("~/projects"))
+        );
+        assert_eq!(
+            parse_command("mkdir a b").unwrap(),
+            AppCommand::Mkdir(vec![String::from("a"), String::from("b")])
+        );
+        assert_eq!(
+            parse_command("hidden off").unwrap(),
+            AppCommand::Hidden(Some(false))
+        );
+        assert_eq!(
+            parse_command("q").unwrap(),
+            AppCommand::Action(Action::Quit)
+        );
+        assert_eq!(
+            parse_command("cursor_bottom").unwrap(),
+            AppCommand::Action(Action::CursorBottom)
+        );
+        assert_eq!(
+            parse_command("sort natural").unwrap(),
+            AppCommand::Sort(SortMode::Natural)
+        );
+        assert_eq!(
+            parse_command("columns size name").unwrap(),
+            AppCommand::Columns(vec![Column::Size, Column::Name])
+        );
+        assert!(parse_command("columns size").is_err());
+        assert_eq!(
+            parse_command("theme light").unwrap(),
+            AppCommand::Theme(Some(String::from("light")))
+        );
+        assert!(parse_command("sort random").is_err());
+        assert!(parse_command("rename").is_err());
+        assert!(parse_command("cursor_bottom now").is_err());
+        assert!(parse_command("explode").is_err());
+        assert!(parse_command("").is_err());
+    }
+
+    #[test]
+    fn test_complete_command_name() {
+        assert_eq!(complete_command_name("mk"), vec!["mkdir"]);
+        assert!(complete_command_name("open_").len() > 1);
+        assert!(complete_command_name("zzz").is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use std::{collections::HashMap, fs, io::ErrorKind, path::Path};
+
+use serde::Deserialize;
+
+use crate::path;
+
+const CONFIG_FILENAME: &str = "config.toml";
+
+// every section is optional, so a config file only needs to hold what differs from the defaults
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // key sequences such as "g g" or "ctrl+d" mapped to the name of an action
+    pub keymap: HashMap<String, String>,
+    // the long listing's columns in the order they're shown, e.g. ["perms", "size", "name"]
+    pub columns: Option<Vec<String>>,
+    // a built-in theme or the name of a file in the themes directory, without `.toml`
+    pub theme: Option<String>,
+    pub show_icons: bool,
+    // file names, `*.ext`, `*/`, `*@` or `*` mapped to the icon shown for them
+    pub icons: HashMap<String, String>,
+}
+
+impl Config {
+    pub fn load_default() -> Result<Self, String> {
+        match path::get_config_dir() {
+            Some(config_dir) => Config::load(config_dir.join(CONFIG_FILENAME)),
+            None => Ok(Config::default()),
+        }
+    }
+
+    // a missing config file isn't an error, it just means everything is left at the defaults
+    pub fn load<T: AsRef<Path>>(filepath: T) -> Result<Self, String> {
+        let contents = match fs::read_to_string(&filepath) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => return Err(format!("Unable to read config, {e}")),
+        };
+        toml::from_str(&contents).map_err(|e| format!("Invalid config, {}", e.message()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_keymap() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join(CONFIG_FILENAME);
+        fs::write(&test_filepath, "[keymap]\n\"g g\" = \"cursor_top\"\n").unwrap();
+
+        let config = Config::load(&test_filepath).unwrap();
+        assert_eq!(config.keymap["g g"], "cursor_top");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_load_columns() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join(CONFIG_FILENAME);
+        fs::write(&test_filepath, "columns = [\"size\", \"name\"]\n").unwrap();
+
+        let config = Config::load(&test_filepath).unwrap();
+        assert_eq!(config.columns.unwrap(), vec!["size", "name"]);
+        assert!(config.keymap.is_empty());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_load_icons() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join(CONFIG_FILENAME);
+        fs::write(
+            &test_filepath,
+            "show_icons = true\n[icons]\n\"*.rs\" = \"R\"\n",
+        )
+        .unwrap();
+
+        let config = Config::load(&test_filepath).unwrap();
+        assert!(config.show_icons);
+        assert_eq!(config.icons["*.rs"], "R");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_load_missing_and_invalid() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join(CONFIG_FILENAME);
+        assert!(Config::load(&test_filepath).unwrap().keymap.is_empty());
+
+        fs::write(&test_filepath, "[keymaps]\n").unwrap();
+        assert!(Config::load(&test_filepath).is_err());
+        test_dir.close().unwrap();
+    }
+}
@@ -2,19 +2,32 @@ use log::error;
 use std::error::Error;
 use std::io;
 use std::path::Path;
+use std::time::Duration;
 
-use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind};
+use crossterm::execute;
 use ratatui::prelude::Backend;
 use ratatui::Terminal;
 
 use crate::app::{App, AppState};
 use crate::ui;
 
+// how long to wait for input before checking on background work such as the finder
+const TICK_RATE: Duration = Duration::from_millis(50);
+
 pub fn run<T: AsRef<Path>>(init_dir: T) -> Result<(), Box<dyn Error>> {
     let mut terminal = ratatui::init();
+    // the app still works from the keyboard without the mouse, and returning early here would
+    // leave the terminal in raw mode
+    if let Err(err) = execute!(io::stdout(), EnableMouseCapture) {
+        error!("Unable to enable mouse capture: {err:?}");
+    }
     let app = App::new(init_dir);
     let app_result = run_app(&mut terminal, app);
 
+    if let Err(err) = execute!(io::stdout(), DisableMouseCapture) {
+        error!("Unable to disable mouse capture: {err:?}");
+    }
     ratatui::restore();
 
     if let Err(err) = app_result {
@@ -26,16 +39,31 @@ pub fn run<T: AsRef<Path>>(init_dir: T) -> Result<(), Box<dyn Error>> {
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()> {
     // TODO find a better way to detect file system changes if possible
     app.refresh_dirlist();
+    // nothing changes on screen between events unless background work brings in results
+    let mut needs_redraw = true;
     while app.app_state != AppState::Exit {
-        terminal.draw(|f| {
-            ui::draw(f, &mut app);
-        })?;
+        if needs_redraw {
+            terminal.draw(|f| {
+                ui::draw(f, &mut app);
+            })?;
+            needs_redraw = false;
+        }
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                app.handle_keypress(key);
+        if event::poll(TICK_RATE)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    app.handle_keypress(key);
+                    needs_redraw = true;
+                }
+                Event::Mouse(mouse) => {
+                    app.handle_mouse(mouse);
+                    needs_redraw = true;
+                }
+                Event::Resize(_, _) => needs_redraw = true,
+                _ => {}
             }
         }
+        needs_redraw |= app.tick();
     }
     Ok(())
 }
@@ -2,6 +2,7 @@ use std::{
     fs::{self, DirEntry},
     io,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use log::{debug, error};
@@ -90,6 +91,34 @@ pub fn copy_dir<T: AsRef<Path>>(
     Ok(())
 }
 
+// like the touch command, creates the file if it's missing and updates its modified time
+pub fn touch_file<T: AsRef<Path>>(filepath: T) -> io::Result<()> {
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filepath)?;
+    file.set_modified(SystemTime::now())
+}
+
+// renames within the same directory, refusing to replace anything already there
+pub fn rename_entry<T: AsRef<Path>>(src_entry: T, new_name: &str) -> io::Result<PathBuf> {
+    if new_name.is_empty() || new_name.contains('/') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the new name can't be empty or contain /",
+        ));
+    }
+    let dest_entry = src_entry.as_ref().with_file_name(new_name);
+    if dest_entry.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{new_name} already exists"),
+        ));
+    }
+    fs::rename(src_entry, &dest_entry)?;
+    Ok(dest_entry)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,4 +270,22 @@ mod tests {
         src_dir.close().unwrap();
         dest_dir.close().unwrap();
     }
+
+    #[test]
+    fn test_touch_and_rename() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join("test_file.txt");
+        touch_file(&test_filepath).unwrap();
+        assert!(test_filepath.is_file());
+        touch_file(&test_filepath).unwrap();
+
+        let renamed_path = rename_entry(&test_filepath, "renamed.txt").unwrap();
+        assert_eq!(renamed_path, test_dir.path().join("renamed.txt"));
+        assert!(!test_filepath.exists());
+
+        touch_file(&test_filepath).unwrap();
+        assert!(rename_entry(&test_filepath, "renamed.txt").is_err());
+        assert!(rename_entry(&test_filepath, "sub/renamed.txt").is_err());
+        test_dir.close().unwrap();
+    }
 }
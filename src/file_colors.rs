@@ -0,0 +1,107 @@
+use std::{fs::DirEntry, sync::LazyLock};
+
+use lscolors::LsColors;
+use ratatui::style::{Color, Modifier, Style};
+
+// without LS_COLORS the same scheme as GNU's dircolors is used
+static LS_COLORS: LazyLock<LsColors> = LazyLock::new(|| LsColors::from_env().unwrap_or_default());
+
+pub fn entry_style(entry: &DirEntry) -> Style {
+    style_for(&LS_COLORS, entry)
+}
+
+fn style_for(ls_colors: &LsColors, entry: &DirEntry) -> Style {
+    ls_colors.style_for(entry).map_or(Style::new(), to_style)
+}
+
+fn to_style(ls_style: &lscolors::Style) -> Style {
+    let mut style = Style::new();
+    if let Some(color) = &ls_style.foreground {
+        style = style.fg(to_color(color));
+    }
+    if let Some(color) = &ls_style.background {
+        style = style.bg(to_color(color));
+    }
+
+    let font_style = &ls_style.font_style;
+    let modifiers = [
+        (font_style.bold, Modifier::BOLD),
+        (font_style.dimmed, Modifier::DIM),
+        (font_style.italic, Modifier::ITALIC),
+        (font_style.underline, Modifier::UNDERLINED),
+        (font_style.slow_blink, Modifier::SLOW_BLINK),
+        (font_style.rapid_blink, Modifier::RAPID_BLINK),
+        (font_style.reverse, Modifier::REVERSED),
+        (font_style.hidden, Modifier::HIDDEN),
+        (font_style.strikethrough, Modifier::CROSSED_OUT),
+    ];
+    modifiers
+        .into_iter()
+        .filter(|(is_set, _)| *is_set)
+        .fold(style, |style, (_, modifier)| style.add_modifier(modifier))
+}
+
+// ratatui names the dim ANSI white gray and the bright one white
+fn to_color(ls_color: &lscolors::Color) -> Color {
+    match ls_color {
+        lscolors::Color::Black => Color::Black,
+        lscolors::Color::Red => Color::Red,
+        lscolors::Color::Green => Color::Green,
+        lscolors::Color::Yellow => Color::Yellow,
+        lscolors::Color::Blue => Color::Blue,
+        lscolors::Color::Magenta => Color::Magenta,
+        lscolors::Color::Cyan => Color::Cyan,
+        lscolors::Color::White => Color::Gray,
+        lscolors::Color::BrightBlack => Color::DarkGray,
+        lscolors::Color::BrightRed => Color::LightRed,
+        lscolors::Color::BrightGreen => Color::LightGreen,
+        lscolors::Color::BrightYellow => Color::LightYellow,
+        lscolors::Color::BrightBlue => Color::LightBlue,
+        lscolors::Color::BrightMagenta => Color::LightMagenta,
+        lscolors::Color::BrightCyan => Color::LightCyan,
+        lscolors::Color::BrightWhite => Color::White,
+        lscolors::Color::Fixed(idx) => Color::Indexed(*idx),
+        lscolors::Color::RGB(r, g, b) => Color::Rgb(*r, *g, *b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path;
+    use std::{fs, os::unix::fs::PermissionsExt};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_entry_styles() {
+        let test_dir = tempdir().unwrap();
+        fs::create_dir(test_dir.path().join("a_dir")).unwrap();
+        fs::write(test_dir.path().join("b_file.rs"), "").unwrap();
+        fs::write(test_dir.path().join("c_script"), "").unwrap();
+        fs::set_permissions(
+            test_dir.path().join("c_script"),
+            fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        fs::write(test_dir.path().join("d_file.txt"), "").unwrap();
+
+        let ls_colors = LsColors::from_string("di=01;34:ex=38;5;208:*.rs=4;32");
+        let mut items = path::get_dir_items(test_dir.path(), &false);
+        items.sort_by_key(|x| x.file_name());
+        let styles: Vec<Style> = items.iter().map(|x| style_for(&ls_colors, x)).collect();
+
+        assert_eq!(
+            styles[0],
+            Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            styles[1],
+            Style::new()
+                .fg(Color::Green)
+                .add_modifier(Modifier::UNDERLINED)
+        );
+        assert_eq!(styles[2], Style::new().fg(Color::Indexed(208)));
+        assert_eq!(styles[3], Style::new());
+        test_dir.close().unwrap();
+    }
+}
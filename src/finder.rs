@@ -0,0 +1,247 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use ratatui::widgets::ListState;
+
+use crate::{fuzzy::fuzzy_match, path};
+
+// caps how much of the walk is taken in and how many candidates are scored per tick or
+// keystroke, so the UI keeps up with large trees
+const MAX_RESULTS_PER_POLL: usize = 10_000;
+const MAX_SCORED_PER_POLL: usize = 5_000;
+
+pub struct FinderMatch {
+    pub candidate_idx: usize,
+    pub score: i64,
+    pub matched_indices: Vec<usize>,
+}
+
+// the subtree is walked on a separate thread and streamed back over a channel, dropping the
+// finder drops the receiver which stops the walk on its next send
+pub struct Finder {
+    pub root: PathBuf,
+    pub query: String,
+    pub candidates: Vec<String>,
+    pub matches: Vec<FinderMatch>,
+    pub state: ListState,
+    pub is_walking: bool,
+    // candidates not yet scored against the current query
+    unscored: Vec<usize>,
+    receiver: Receiver<String>,
+}
+
+impl Finder {
+    pub fn new<T: AsRef<Path>>(root: T, show_hidden: bool) -> Self {
+        let root = root.as_ref().to_path_buf();
+        let (sender, receiver) = mpsc::channel();
+
+        let walk_root = root.clone();
+        thread::spawn(move || {
+            path::walk_dir(&walk_root, &show_hidden, &mut |entry| {
+                let entry_path = entry.path();
+                let relative_path = entry_path
+                    .strip_prefix(&walk_root)
+                    .unwrap_or(entry_path.as_path());
+                sender
+                    .send(relative_path.to_string_lossy().to_string())
+                    .is_ok()
+            });
+        });
+
+        Finder {
+            root,
+            query: String::new(),
+            candidates: Vec::new(),
+            matches: Vec::new(),
+            state: ListState::default(),
+            is_walking: true,
+            unscored: Vec::new(),
+            receiver,
+        }
+    }
+
+    // takes in whatever the walk has found since the last poll and scores some more of the
+    // candidates, returns whether anything changed
+    pub fn poll(&mut self) -> bool {
+        let was_walking = self.is_walking;
+        let first_new_idx = self.candidates.len();
+        while self.candidates.len() - first_new_idx < MAX_RESULTS_PER_POLL {
+            match self.receiver.try_recv() {
+                Ok(candidate) => self.candidates.push(candidate),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.is_walking = false;
+                    break;
+                }
+            }
+        }
+        self.unscored.extend(first_new_idx..self.candidates.len());
+
+        let has_scored = self.rank_candidates();
+        if self.state.selected().is_none() && !self.matches.is_empty() {
+            self.state.select_first();
+        }
+        has_scored || was_walking != self.is_walking
+    }
+
+    // a longer query can only match what the shorter one did, so typing on narrows the
+    // existing matches rather than scoring every candidate again
+    pub fn set_query(&mut self, query: String) {
+        if query.starts_with(&self.query) {
+            self.unscored
+                .extend(self.matches.iter().map(|x| x.candidate_idx));
+        } else {
+            self.unscored = (0..self.candidates.len()).collect();
+        }
+        self.query = query;
+        self.matches.clear();
+        self.rank_candidates();
+        self.state.select_first();
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.is_walking || !self.unscored.is_empty()
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        self.state.select_previous();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        self.state.select_next();
+    }
+
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        self.state
+            .selected()
+            .and_then(|idx| self.matches.get(idx))
+            .map(|x| self.root.join(&self.candidates[x.candidate_idx]))
+    }
+
+    // scores the next batch of unscored candidates and merges them into the already sorted
+    // matches, returns whether there was anything to score
+    fn rank_candidates(&mut self) -> bool {
+        if self.unscored.is_empty() {
+            return false;
+        }
+        let batch = self
+            .unscored
+            .split_off(self.unscored.len().saturating_sub(MAX_SCORED_PER_POLL));
+        let mut new_matches: Vec<FinderMatch> = batch
+            .into_iter()
+            .filter_map(|candidate_idx| {
+                let (score, matched_indices) =
+                    fuzzy_match(&self.query, &self.candidates[candidate_idx])?;
+                Some(FinderMatch {
+                    candidate_idx,
+                    score,
+                    matched_indices,
+                })
+            })
+            .collect();
+
+        // shorter paths win ties since they are usually closer to what was asked for
+        let candidates = &self.candidates;
+        let sort_key =
+            |x: &FinderMatch| (-x.score, candidates[x.candidate_idx].len(), x.candidate_idx);
+        new_matches.sort_by_key(sort_key);
+
+        let mut merged = Vec::with_capacity(self.matches.len() + new_matches.len());
+        let mut old_matches = std::mem::take(&mut self.matches).into_iter().peekable();
+        let mut new_matches = new_matches.into_iter().peekable();
+        while let (Some(old_match), Some(new_match)) = (old_matches.peek(), new_matches.peek()) {
+            if sort_key(old_match) <= sort_key(new_match) {
+                merged.extend(old_matches.next());
+            } else {
+                merged.extend(new_matches.next());
+            }
+        }
+        merged.extend(old_matches);
+        merged.extend(new_matches);
+        self.matches = merged;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn wait_for_walk(finder: &mut Finder) {
+        while finder.is_busy() {
+            finder.poll();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_finder_walks_subtree() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_subdir.join("test_file.txt")).unwrap();
+        let _hidden_file = fs::File::create(test_subdir.join(".hidden_file")).unwrap();
+
+        let mut finder = Finder::new(test_dir.path(), false);
+        wait_for_walk(&mut finder);
+
+        assert_eq!(finder.candidates.len(), 2);
+        assert_eq!(finder.matches.len(), 2);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_finder_ranks_query() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _first_file = fs::File::create(test_subdir.join("test_file.txt")).unwrap();
+        let _second_file = fs::File::create(test_subdir.join("other.txt")).unwrap();
+
+        let mut finder = Finder::new(test_dir.path(), false);
+        wait_for_walk(&mut finder);
+        finder.set_query("tfile".to_string());
+
+        assert_eq!(finder.matches.len(), 1);
+        assert_eq!(
+            finder.selected_path().unwrap(),
+            test_subdir.join("test_file.txt")
+        );
+
+        // narrowing keeps only what still matches, going back scores everything again
+        finder.set_query("tfileq".to_string());
+        assert!(finder.matches.is_empty());
+        finder.set_query("txt".to_string());
+        assert_eq!(finder.matches.len(), 2);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_finder_merges_batches_in_order() {
+        let test_dir = tempdir().unwrap();
+        let mut finder = Finder::new(test_dir.path(), false);
+        wait_for_walk(&mut finder);
+
+        let candidate_count = MAX_SCORED_PER_POLL + 10;
+        finder.candidates = (0..candidate_count).map(|x| format!("{x}.txt")).collect();
+        finder.unscored.extend(0..candidate_count);
+        finder.set_query(String::from("txt"));
+        assert_eq!(finder.matches.len(), MAX_SCORED_PER_POLL);
+        assert!(finder.is_busy());
+
+        wait_for_walk(&mut finder);
+        assert_eq!(finder.matches.len(), candidate_count);
+        let candidates = &finder.candidates;
+        assert!(finder.matches.windows(2).all(|x| {
+            let key =
+                |x: &FinderMatch| (-x.score, candidates[x.candidate_idx].len(), x.candidate_idx);
+            key(&x[0]) <= key(&x[1])
+        }));
+        test_dir.close().unwrap();
+    }
+}
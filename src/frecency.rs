@@ -0,0 +1,303 @@
+use std::{
+    ffi::OsStr,
+    fs,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::{debug, error};
+use ratatui::widgets::ListState;
+
+use crate::{fuzzy::fuzzy_match, path};
+
+const FRECENCY_FILENAME: &str = "frecency";
+// the least used directories are forgotten once the database grows past this
+const MAX_FRECENCY_ENTRIES: usize = 1000;
+
+const HOUR_SECS: u64 = 60 * 60;
+const DAY_SECS: u64 = 24 * HOUR_SECS;
+const WEEK_SECS: u64 = 7 * DAY_SECS;
+
+pub struct FrecencyEntry {
+    pub path: PathBuf,
+    pub visits: u32,
+    pub last_visit: u64,
+}
+
+impl FrecencyEntry {
+    // the same weighting zoxide uses, recent visits count for a lot more than old ones
+    fn score(&self, now: u64) -> f64 {
+        let elapsed = now.saturating_sub(self.last_visit);
+        let recency_weight = if elapsed < HOUR_SECS {
+            4.0
+        } else if elapsed < DAY_SECS {
+            2.0
+        } else if elapsed < WEEK_SECS {
+            0.5
+        } else {
+            0.25
+        };
+        self.visits as f64 * recency_weight
+    }
+}
+
+pub struct FrecencyMatch {
+    pub entry_idx: usize,
+    pub matched_indices: Vec<usize>,
+}
+
+// visited directories are kept in a file in the data directory, one
+// `visits<TAB>last visit<TAB>path` per line with the path's raw bytes so non UTF-8 paths
+// come back unchanged
+pub struct Frecency {
+    pub entries: Vec<FrecencyEntry>,
+    pub query: String,
+    pub matches: Vec<FrecencyMatch>,
+    pub state: ListState,
+    filepath: Option<PathBuf>,
+    // visits are recorded on every directory change, so the file is only written once on exit
+    // rather than on each of them
+    is_dirty: bool,
+}
+
+impl Frecency {
+    pub fn load_default() -> Self {
+        Frecency::load(path::get_data_dir().map(|x| x.join(FRECENCY_FILENAME)))
+    }
+
+    pub fn load(filepath: Option<PathBuf>) -> Self {
+        let mut entries = Vec::new();
+        if let Some(contents) = filepath.as_ref().and_then(|x| fs::read(x).ok()) {
+            for line in contents.split(|x| *x == b'\n').filter(|x| !x.is_empty()) {
+                match parse_entry(line) {
+                    Some(entry) => entries.push(entry),
+                    None => debug!(
+                        "Skipping malformed frecency entry: {:?}",
+                        String::from_utf8_lossy(line)
+                    ),
+                }
+            }
+        }
+
+        Frecency {
+            entries,
+            query: String::new(),
+            matches: Vec::new(),
+            state: ListState::default(),
+            filepath,
+            is_dirty: false,
+        }
+    }
+
+    pub fn record<T: AsRef<Path>>(&mut self, visited_dir: T) {
+        let visited_dir = visited_dir.as_ref();
+        let now = unix_now();
+        match self.entries.iter_mut().find(|x| x.path == visited_dir) {
+            Some(entry) => {
+                entry.visits += 1;
+                entry.last_visit = now;
+            }
+            None => self.entries.push(FrecencyEntry {
+                path: visited_dir.to_path_buf(),
+                visits: 1,
+                last_visit: now,
+            }),
+        }
+
+        if self.entries.len() > MAX_FRECENCY_ENTRIES {
+            self.entries
+                .sort_by(|a, b| b.score(now).total_cmp(&a.score(now)));
+            self.entries.truncate(MAX_FRECENCY_ENTRIES);
+        }
+        self.is_dirty = true;
+    }
+
+    pub fn remove<T: AsRef<Path>>(&mut self, removed_dir: T) {
+        self.entries.retain(|x| x.path != removed_dir.as_ref());
+        self.is_dirty = true;
+        self.set_query(self.query.clone());
+    }
+
+    // matching directories are ranked by frecency rather than by how well they matched
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        let now = unix_now();
+
+        let mut matches: Vec<(f64, FrecencyMatch)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(entry_idx, entry)| {
+                let (_, matched_indices) = fuzzy_match(&self.query, &entry.path.to_string_lossy())?;
+                Some((
+                    entry.score(now),
+                    FrecencyMatch {
+                        entry_idx,
+                        matched_indices,
+                    },
+                ))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        self.matches = matches.into_iter().map(|(_, x)| x).collect();
+        self.state = ListState::default();
+        self.state.select_first();
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        self.state.select_previous();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        self.state.select_next();
+    }
+
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        self.state
+            .selected()
+            .and_then(|idx| self.matches.get(idx))
+            .map(|x| self.entries[x.entry_idx].path.clone())
+    }
+
+    pub fn save(&mut self) {
+        let Some(filepath) = self.filepath.as_ref().filter(|_| self.is_dirty) else {
+            return;
+        };
+
+        let mut contents = Vec::new();
+        for entry in &self.entries {
+            // a newline would split the entry in two when it's read back
+            let path_bytes = entry.path.as_os_str().as_bytes();
+            if path_bytes.contains(&b'\n') {
+                debug!("Not saving visited directory {:?}", entry.path);
+                continue;
+            }
+            contents
+                .extend_from_slice(format!("{}\t{}\t", entry.visits, entry.last_visit).as_bytes());
+            contents.extend_from_slice(path_bytes);
+            contents.push(b'\n');
+        }
+        let save_result = fs::create_dir_all(path::get_parent_dir(filepath))
+            .and_then(|_| fs::write(filepath, contents));
+        match save_result {
+            Ok(_) => self.is_dirty = false,
+            Err(e) => error!("Unable to save visited directories: {e:?}"),
+        }
+    }
+}
+
+impl Drop for Frecency {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
+
+fn parse_entry(line: &[u8]) -> Option<FrecencyEntry> {
+    let mut fields = line.splitn(3, |x| *x == b'\t');
+    let visits = str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+    let last_visit = str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+    let path = PathBuf::from(OsStr::from_bytes(fields.next()?));
+    Some(FrecencyEntry {
+        path,
+        visits,
+        last_visit,
+    })
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_frecency_persists() {
+        let test_dir = tempdir().unwrap();
+        let frecency_filepath = test_dir.path().join("data").join(FRECENCY_FILENAME);
+
+        let mut frecency = Frecency::load(Some(frecency_filepath.clone()));
+        frecency.record("/tmp");
+        frecency.record("/tmp");
+        frecency.record("/home");
+        // nothing is written until the visits are saved
+        assert!(!frecency_filepath.exists());
+        frecency.save();
+
+        let frecency = Frecency::load(Some(frecency_filepath));
+        assert_eq!(frecency.entries.len(), 2);
+        assert_eq!(frecency.entries[0].path, PathBuf::from("/tmp"));
+        assert_eq!(frecency.entries[0].visits, 2);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_frecency_non_utf8_path() {
+        let test_dir = tempdir().unwrap();
+        let frecency_filepath = test_dir.path().join(FRECENCY_FILENAME);
+        let non_utf8_dir = PathBuf::from(OsStr::from_bytes(b"/srv/caf\xe9\tbar"));
+
+        let mut frecency = Frecency::load(Some(frecency_filepath.clone()));
+        frecency.record(&non_utf8_dir);
+        frecency.record("/srv/two\nlines");
+        drop(frecency);
+
+        let frecency = Frecency::load(Some(frecency_filepath));
+        assert_eq!(frecency.entries.len(), 1);
+        assert_eq!(frecency.entries[0].path, non_utf8_dir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_frecency_score() {
+        let now = unix_now();
+        let recent_entry = FrecencyEntry {
+            path: PathBuf::from("/tmp"),
+            visits: 1,
+            last_visit: now,
+        };
+        let old_entry = FrecencyEntry {
+            path: PathBuf::from("/home"),
+            visits: 4,
+            last_visit: now - 2 * WEEK_SECS,
+        };
+
+        assert_eq!(recent_entry.score(now), 4.0);
+        assert_eq!(old_entry.score(now), 1.0);
+    }
+
+    #[test]
+    fn test_frecency_ranking() {
+        let mut frecency = Frecency::load(None);
+        frecency.record("/srv/project_a");
+        frecency.record("/srv/project_b");
+        frecency.record("/srv/project_b");
+        frecency.record("/home");
+
+        frecency.set_query("proj".to_string());
+
+        assert_eq!(frecency.matches.len(), 2);
+        assert_eq!(
+            frecency.selected_path(),
+            Some(PathBuf::from("/srv/project_b"))
+        );
+    }
+
+    #[test]
+    fn test_frecency_remove() {
+        let mut frecency = Frecency::load(None);
+        frecency.record("/srv/project_a");
+        frecency.record("/srv/project_b");
+
+        frecency.remove("/srv/project_a");
+
+        assert_eq!(frecency.entries.len(), 1);
+        assert_eq!(frecency.matches.len(), 1);
+    }
+}
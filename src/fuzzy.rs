@@ -0,0 +1,33 @@
+use std::sync::LazyLock;
+
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+static MATCHER: LazyLock<SkimMatcherV2> = LazyLock::new(|| SkimMatcherV2::default().smart_case());
+
+// returns the match score along with the char indices of `text` that matched the pattern
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+    MATCHER.fuzzy_indices(text, pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match() {
+        let (_, indices) = fuzzy_match("crgo", "Cargo.toml").unwrap();
+
+        assert_eq!(indices, vec![0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_fuzzy_no_match() {
+        assert!(fuzzy_match("xyz", "Cargo.toml").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_smart_case() {
+        assert!(fuzzy_match("readme", "README.md").is_some());
+        assert!(fuzzy_match("README", "readme.md").is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use std::{collections::HashMap, fs::DirEntry, path::Path, sync::OnceLock};
+
+// Nerd Font glyphs, keyed by file name, by `*.` and the lowercase extension, or by `*/`, `*@`
+// and `*` for any other directory, symlink and file
+const DEFAULT_ICONS: &[(&str, &str)] = &[
+    (".git", "\u{e702}"),
+    (".gitignore", "\u{e702}"),
+    (".gitmodules", "\u{e702}"),
+    ("Cargo.toml", "\u{e7a8}"),
+    ("Cargo.lock", "\u{e7a8}"),
+    ("Makefile", "\u{e779}"),
+    ("CMakeLists.txt", "\u{e779}"),
+    ("Dockerfile", "\u{f308}"),
+    ("LICENSE", "\u{f02d}"),
+    ("README.md", "\u{f48a}"),
+    ("*.rs", "\u{e7a8}"),
+    ("*.py", "\u{e606}"),
+    ("*.js", "\u{e74e}"),
+    ("*.ts", "\u{e628}"),
+    ("*.go", "\u{e627}"),
+    ("*.c", "\u{e61e}"),
+    ("*.h", "\u{e61e}"),
+    ("*.cpp", "\u{e61d}"),
+    ("*.java", "\u{e738}"),
+    ("*.sh", "\u{f489}"),
+    ("*.html", "\u{e736}"),
+    ("*.css", "\u{e749}"),
+    ("*.md", "\u{e609}"),
+    ("*.json", "\u{e60b}"),
+    ("*.toml", "\u{e615}"),
+    ("*.yml", "\u{e615}"),
+    ("*.yaml", "\u{e615}"),
+    ("*.lock", "\u{f023}"),
+    ("*.txt", "\u{f15c}"),
+    ("*.pdf", "\u{f1c1}"),
+    ("*.png", "\u{f1c5}"),
+    ("*.jpg", "\u{f1c5}"),
+    ("*.jpeg", "\u{f1c5}"),
+    ("*.gif", "\u{f1c5}"),
+    ("*.svg", "\u{f1c5}"),
+    ("*.mp3", "\u{f001}"),
+    ("*.flac", "\u{f001}"),
+    ("*.wav", "\u{f001}"),
+    ("*.mp4", "\u{f03d}"),
+    ("*.mkv", "\u{f03d}"),
+    ("*.zip", "\u{f410}"),
+    ("*.tar", "\u{f410}"),
+    ("*.gz", "\u{f410}"),
+    ("*.xz", "\u{f410}"),
+    ("*/", "\u{f07b}"),
+    ("*@", "\u{f0c1}"),
+    ("*", "\u{f15b}"),
+];
+
+// set once from the config at startup, with the same keys as `DEFAULT_ICONS` and taking
+// precedence over them. Icons are resolved once per listing rather than on every draw, so
+// they can't depend on anything passed down to the ui.
+static ICON_OVERRIDES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+pub fn set_overrides(overrides: &HashMap<String, String>) {
+    let _ = ICON_OVERRIDES.set(overrides.clone());
+}
+
+pub fn entry_icon(entry: &DirEntry) -> Option<&'static str> {
+    icon_for(ICON_OVERRIDES.get_or_init(HashMap::new), entry)
+}
+
+// the most specific key wins, so a file name beats its extension which beats its type
+fn icon_for<'a>(overrides: &'a HashMap<String, String>, entry: &DirEntry) -> Option<&'a str> {
+    let filename = entry.file_name().to_string_lossy().to_string();
+    let is_symlink = entry.file_type().is_ok_and(|x| x.is_symlink());
+    let is_dir = entry.path().is_dir();
+    let mut keys = vec![filename.clone()];
+    if !is_dir {
+        if let Some(extension) = Path::new(&filename).extension() {
+            keys.push(format!("*.{}", extension.to_string_lossy().to_lowercase()));
+        }
+    }
+    keys.push(String::from(match (is_symlink, is_dir) {
+        (true, _) => "*@",
+        (false, true) => "*/",
+        (false, false) => "*",
+    }));
+
+    keys.iter().find_map(|key| {
+        overrides.get(key).map(String::as_str).or_else(|| {
+            DEFAULT_ICONS
+                .iter()
+                .find(|(icon_key, _)| icon_key == key)
+                .map(|(_, icon)| *icon)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_icon_for() {
+        let test_dir = tempdir().unwrap();
+        fs::create_dir(test_dir.path().join(".git")).unwrap();
+        fs::create_dir(test_dir.path().join("a_dir")).unwrap();
+        fs::write(test_dir.path().join("Cargo.toml"), "").unwrap();
+        fs::write(test_dir.path().join("main.RS"), "").unwrap();
+        fs::write(test_dir.path().join("notes.unknown"), "").unwrap();
+
+        let mut items = path::get_dir_items(test_dir.path(), &true);
+        items.sort_by_key(|x| x.file_name());
+        let no_overrides = HashMap::new();
+        let item_icons: Vec<&str> = items
+            .iter()
+            .filter_map(|x| icon_for(&no_overrides, x))
+            .collect();
+        assert_eq!(
+            item_icons,
+            vec!["\u{e702}", "\u{e7a8}", "\u{f07b}", "\u{e7a8}", "\u{f15b}"]
+        );
+
+        let overrides = HashMap::from([
+            (String::from("*.rs"), String::from("R")),
+            (String::from("*/"), String::from("D")),
+        ]);
+        assert_eq!(icon_for(&overrides, &items[2]), Some("D"));
+        assert_eq!(icon_for(&overrides, &items[3]), Some("R"));
+        assert_eq!(icon_for(&overrides, &items[1]), Some("\u{e7a8}"));
+        test_dir.close().unwrap();
+    }
+}
@@ -0,0 +1,699 @@
+use std::{collections::HashMap, fmt};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+// a key sequence can be mapped to this in the config to remove its default binding
+const UNBOUND_ACTION_NAME: &str = "unbound";
+
+// everything a key can be bound to while browsing
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Action {
+    Quit,
+    ToggleHidden,
+    CycleSort,
+    ToggleSortReverse,
+    ToggleDirsFirst,
+    CycleLayout,
+    TogglePreview,
+    PreviewPageDown,
+    PreviewPageUp,
+    OpenCommandLine,
+    ShowHelp,
+    StartFilter,
+    OpenFinder,
+    OpenContentSearch,
+    OpenPathPrompt,
+    OpenBookmarkPrompt,
+    OpenBookmarks,
+    OpenJump,
+    ToggleTreeMode,
+    ToggleLongListing,
+    ToggleIcons,
+    ToggleExpanded,
+    OpenTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+    Copy,
+    Paste,
+    Move,
+    Trash,
+    GoBack,
+    GoForward,
+    CursorUp,
+    CursorDown,
+    CursorTop,
+    CursorBottom,
+    HalfPageUp,
+    HalfPageDown,
+    PageUp,
+    PageDown,
+    SwitchPanes,
+    Open,
+    NavUp,
+}
+
+// the names actions go by in the config and on the command line, along with what they do
+const ACTIONS: &[(Action, &str, &str)] = &[
+    (Action::Quit, "quit", "Quit"),
+    (
+        Action::ToggleHidden,
+        "toggle_hidden",
+        "Show or hide hidden files",
+    ),
+    (Action::CycleSort, "cycle_sort", "Cycle the sort mode"),
+    (
+        Action::ToggleSortReverse,
+        "toggle_sort_reverse",
+        "Reverse the sort order",
+    ),
+    (
+        Action::ToggleDirsFirst,
+        "toggle_dirs_first",
+        "Keep directories before files",
+    ),
+    (
+        Action::CycleLayout,
+        "cycle_layout",
+        "Cycle the pane layout between single, dual and miller columns",
+    ),
+    (
+        Action::TogglePreview,
+        "toggle_preview",
+        "Show or hide the preview",
+    ),
+    (
+        Action::PreviewPageDown,
+        "preview_page_down",
+        "Scroll the preview down a page",
+    ),
+    (
+        Action::PreviewPageUp,
+        "preview_page_up",
+        "Scroll the preview up a page",
+    ),
+    (
+        Action::OpenCommandLine,
+        "open_command_line",
+        "Open the command line",
+    ),
+    (Action::ShowHelp, "show_help", "Show this help"),
+    (
+        Action::StartFilter,
+        "start_filter",
+        "Filter the current listing",
+    ),
+    (
+        Action::OpenFinder,
+        "open_finder",
+        "Find a file below the current directory",
+    ),
+    (
+        Action::OpenContentSearch,
+        "open_content_search",
+        "Search file contents below the current directory",
+    ),
+    (Action::OpenPathPrompt, "open_path_prompt", "Go to a path"),
+    (
+        Action::OpenBookmarkPrompt,
+        "open_bookmark_prompt",
+        "Bookmark the current directory",
+    ),
+    (
+        Action::OpenBookmarks,
+        "open_bookmarks",
+        "Open the bookmarks",
+    ),
+    (
+        Action::OpenJump,
+        "open_jump",
+        "Jump to a frequently visited directory",
+    ),
+    (
+        Action::ToggleTreeMode,
+        "toggle_tree_mode",
+        "Switch between the tree and the flat listing",
+    ),
+    (
+        Action::ToggleLongListing,
+        "toggle_long_listing",
+        "Show permissions, owner, group, size and modified time for every entry",
+    ),
+    (
+        Action::ToggleIcons,
+        "toggle_icons",
+        "Show or hide file type icons, which need a Nerd Font",
+    ),
+    (
+        Action::ToggleExpanded,
+        "toggle_expanded",
+        "Expand or collapse a directory in the tree",
+    ),
+    (Action::OpenTab, "open_tab", "Open a new tab"),
+    (Action::CloseTab, "close_tab", "Close the current tab"),
+    (Action::NextTab, "next_tab", "Go to the next tab"),
+    (Action::PrevTab, "prev_tab", "Go to the previous tab"),
+    (
+        Action::Copy,
+        "copy",
+        "Put the selected entry in the clipboard",
+    ),
+    (Action::Paste, "paste", "Copy the clipboard entry here"),
+    (Action::Move, "move", "Move the clipboard entry here"),
+    (
+        Action::Trash,
+        "trash",
+        "Move the selected entry to the trash",
+    ),
+    (
+        Action::GoBack,
+        "go_back",
+        "Go back to the previous directory",
+    ),
+    (
+        Action::GoForward,
+        "go_forward",
+        "Go forward to the next directory",
+    ),
+    (Action::CursorUp, "cursor_up", "Move up"),
+    (Action::CursorDown, "cursor_down", "Move down"),
+    (
+        Action::CursorTop,
+        "cursor_top",
+        "Move to the top, or to the entry given by a count",
+    ),
+    (
+        Action::CursorBottom,
+        "cursor_bottom",
+        "Move to the bottom, or to the entry given by a count",
+    ),
+    (Action::HalfPageUp, "half_page_up", "Move up half a page"),
+    (
+        Action::HalfPageDown,
+        "half_page_down",
+        "Move down half a page",
+    ),
+    (Action::PageUp, "page_up", "Move up a page"),
+    (Action::PageDown, "page_down", "Move down a page"),
+    (
+        Action::SwitchPanes,
+        "switch_panes",
+        "Switch between the panes of the dual layout",
+    ),
+    (Action::Open, "open", "Open the selected file or directory"),
+    (Action::NavUp, "nav_up", "Go to the parent directory"),
+];
+
+impl Action {
+    pub fn all() -> impl Iterator<Item = Action> {
+        ACTIONS.iter().map(|(action, _, _)| *action)
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS
+            .iter()
+            .find(|(_, action_name, _)| *action_name == name)
+            .map(|(action, _, _)| *action)
+    }
+
+    pub fn name(&self) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(action, _, _)| action == self)
+            .map_or("", |(_, action_name, _)| action_name)
+    }
+
+    pub fn description(&self) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(action, _, _)| action == self)
+            .map_or("", |(_, _, description)| description)
+    }
+
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        ACTIONS.iter().map(|(_, action_name, _)| *action_name)
+    }
+}
+
+// a single key of a binding, with shift left out for characters since it is already part of
+// the character itself
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        KeyBinding { code, modifiers }
+    }
+
+    const fn plain(code: KeyCode) -> Self {
+        KeyBinding::new(code, KeyModifiers::NONE)
+    }
+
+    const fn char(c: char) -> Self {
+        KeyBinding::plain(KeyCode::Char(c))
+    }
+}
+
+// parses the config's way of writing a key, such as "j", "G", "ctrl+d", "alt+left" or "space"
+impl TryFrom<&str> for KeyBinding {
+    type Error = String;
+
+    fn try_from(key_name: &str) -> Result<Self, Self::Error> {
+        // the last part is the key itself, so "ctrl++" binds the plus key
+        let (modifier_names, code_name) = match key_name.strip_suffix("++") {
+            Some(modifier_names) => (modifier_names, "+"),
+            None => key_name.rsplit_once('+').unwrap_or(("", key_name)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier_name in modifier_names.split('+').filter(|x| !x.is_empty()) {
+            modifiers |= match modifier_name.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => {
+                    return Err(format!(
+                        "Unknown modifier {modifier_name:?} in {key_name:?}"
+                    ))
+                }
+            };
+        }
+
+        let mut chars = code_name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match code_name.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "del" | "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                function_name => match function_name
+                    .strip_prefix('f')
+                    .and_then(|x| x.parse::<u8>().ok())
+                {
+                    Some(function_number) => KeyCode::F(function_number),
+                    None => return Err(format!("Unknown key {key_name:?}")),
+                },
+            },
+        };
+
+        // shift is folded into the key, the same way it arrives from the terminal
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            _ => code,
+        };
+        Ok(KeyBinding::from(KeyEvent::new(code, modifiers)))
+    }
+}
+
+// written the same way as in the config, so what the help shows can be put in a keymap
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(function_number) => write!(f, "f{function_number}"),
+            KeyCode::Delete => write!(f, "del"),
+            code => write!(f, "{}", code.to_string().to_lowercase().replace(' ', "")),
+        }
+    }
+}
+
+impl From<KeyEvent> for KeyBinding {
+    fn from(key: KeyEvent) -> Self {
+        let modifiers = match key.code {
+            KeyCode::Char(_) | KeyCode::BackTab => key.modifiers - KeyModifiers::SHIFT,
+            _ => key.modifiers,
+        };
+        KeyBinding::new(key.code, modifiers)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum KeyLookup {
+    Matched(Action),
+    // the keys so far are the start of a longer binding
+    Pending,
+    Unbound,
+}
+
+pub struct Keymap {
+    pub bindings: Vec<(Vec<KeyBinding>, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let single_key_bindings = [
+            (KeyBinding::char('q'), Action::Quit),
+            (KeyBinding::char('h'), Action::ToggleHidden),
+            (KeyBinding::char('s'), Action::CycleSort),
+            (KeyBinding::char('r'), Action::ToggleSortReverse),
+            (KeyBinding::char('D'), Action::ToggleDirsFirst),
+            (KeyBinding::char('v'), Action::CycleLayout),
+            (KeyBinding::char('i'), Action::TogglePreview),
+            (KeyBinding::char('J'), Action::PreviewPageDown),
+            (KeyBinding::char('K'), Action::PreviewPageUp),
+            (KeyBinding::char(':'), Action::OpenCommandLine),
+            (KeyBinding::char('?'), Action::ShowHelp),
+            (KeyBinding::char('/'), Action::StartFilter),
+            (KeyBinding::char('f'), Action::OpenFinder),
+            (KeyBinding::char('F'), Action::OpenContentSearch),
+            (KeyBinding::char('m'), Action::OpenBookmarkPrompt),
+            (KeyBinding::char('\''), Action::OpenBookmarks),
+            (KeyBinding::char('z'), Action::OpenJump),
+            (KeyBinding::char('T'), Action::ToggleTreeMode),
+            (KeyBinding::char('L'), Action::ToggleLongListing),
+            (KeyBinding::char('I'), Action::ToggleIcons),
+            (KeyBinding::char('e'), Action::ToggleExpanded),
+            (KeyBinding::char('t'), Action::OpenTab),
+            (KeyBinding::char('w'), Action::CloseTab),
+            (KeyBinding::char('>'), Action::NextTab),
+            (KeyBinding::char('<'), Action::PrevTab),
+            (KeyBinding::char('c'), Action::Copy),
+            (KeyBinding::char('y'), Action::Copy),
+            (KeyBinding::char('p'), Action::Paste),
+            (KeyBinding::char('x'), Action::Move),
+            (KeyBinding::plain(KeyCode::Delete), Action::Trash),
+            (KeyBinding::char('['), Action::GoBack),
+            (KeyBinding::char(']'), Action::GoForward),
+            (
+                KeyBinding::new(KeyCode::Left, KeyModifiers::ALT),
+                Action::GoBack,
+            ),
+            (
+                KeyBinding::new(KeyCode::Right, KeyModifiers::ALT),
+                Action::GoForward,
+            ),
+            (KeyBinding::char('k'), Action::CursorUp),
+            (KeyBinding::plain(KeyCode::Up), Action::CursorUp),
+            (KeyBinding::char('j'), Action::CursorDown),
+            (KeyBinding::plain(KeyCode::Down), Action::CursorDown),
+            (KeyBinding::plain(KeyCode::Home), Action::CursorTop),
+            (KeyBinding::char('G'), Action::CursorBottom),
+            (KeyBinding::plain(KeyCode::End), Action::CursorBottom),
+            (
+                KeyBinding::new(KeyCode::Char('u'), KeyModifiers::CONTROL),
+                Action::HalfPageUp,
+            ),
+            (
+                KeyBinding::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+                Action::HalfPageDown,
+            ),
+            (KeyBinding::plain(KeyCode::PageUp), Action::PageUp),
+            (KeyBinding::plain(KeyCode::PageDown), Action::PageDown),
+            (KeyBinding::plain(KeyCode::Tab), Action::SwitchPanes),
+            (KeyBinding::plain(KeyCode::BackTab), Action::SwitchPanes),
+            (KeyBinding::plain(KeyCode::Enter), Action::Open),
+            (KeyBinding::char(' '), Action::Open),
+            (KeyBinding::plain(KeyCode::Right), Action::Open),
+            (KeyBinding::plain(KeyCode::Esc), Action::NavUp),
+            (KeyBinding::plain(KeyCode::Backspace), Action::NavUp),
+            (KeyBinding::plain(KeyCode::Left), Action::NavUp),
+        ];
+
+        let mut bindings: Vec<(Vec<KeyBinding>, Action)> = single_key_bindings
+            .into_iter()
+            .map(|(key, action)| (vec![key], action))
+            .collect();
+        bindings.push((
+            vec![KeyBinding::char('g'), KeyBinding::char('g')],
+            Action::CursorTop,
+        ));
+        bindings.push((
+            vec![KeyBinding::char('g'), KeyBinding::char('d')],
+            Action::OpenPathPrompt,
+        ));
+        Keymap { bindings }
+    }
+}
+
+impl Keymap {
+    // one line per action with every key sequence bound to it, in the order actions are listed
+    pub fn help_entries(&self) -> Vec<(String, Action)> {
+        Action::all()
+            .map(|action| {
+                let key_sequences: Vec<String> = self
+                    .bindings
+                    .iter()
+                    .filter(|(_, bound_action)| *bound_action == action)
+                    .map(|(keys, _)| key_sequence_string(keys))
+                    .collect();
+                (key_sequences.join(", "), action)
+            })
+            .collect()
+    }
+
+    // the config's bindings replace any default binding of the same key sequence. two entries
+    // for the same keys, or a sequence that starts with another bound sequence and so can never
+    // be reached, are errors rather than left to whichever entry happens to be applied last
+    pub fn with_config(keymap_config: &HashMap<String, String>) -> Result<Self, String> {
+        let mut config_entries: Vec<(&String, &String)> = keymap_config.iter().collect();
+        config_entries.sort();
+
+        let mut keymap = Keymap::default();
+        let mut configured_keys: Vec<(Vec<KeyBinding>, &String)> = Vec::new();
+        for (key_sequence, action_name) in config_entries {
+            let keys = parse_key_sequence(key_sequence)?;
+            if let Some((_, other_sequence)) = configured_keys.iter().find(|(x, _)| *x == keys) {
+                return Err(format!(
+                    "{other_sequence:?} and {key_sequence:?} are the same keys"
+                ));
+            }
+            keymap
+                .bindings
+                .retain(|(binding_keys, _)| binding_keys != &keys);
+            configured_keys.push((keys.clone(), key_sequence));
+            if action_name == UNBOUND_ACTION_NAME {
+                continue;
+            }
+
+            let action = Action::from_name(action_name)
+                .ok_or_else(|| format!("Unknown action {action_name:?} for {key_sequence:?}"))?;
+            keymap.bindings.push((keys, action));
+        }
+
+        for (prefix_keys, _) in keymap.bindings.iter() {
+            if let Some((shadowed_keys, _)) = keymap
+                .bindings
+                .iter()
+                .find(|(x, _)| x.len() > prefix_keys.len() && x.starts_with(prefix_keys))
+            {
+                return Err(format!(
+                    "{:?} makes {:?} unreachable",
+                    key_sequence_string(prefix_keys),
+                    key_sequence_string(shadowed_keys)
+                ));
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn lookup(&self, keys: &[KeyBinding]) -> KeyLookup {
+        let mut lookup = KeyLookup::Unbound;
+        for (binding_keys, action) in self.bindings.iter() {
+            if binding_keys == keys {
+                return KeyLookup::Matched(*action);
+            }
+            if binding_keys.starts_with(keys) {
+                lookup = KeyLookup::Pending;
+            }
+        }
+        lookup
+    }
+}
+
+fn key_sequence_string(keys: &[KeyBinding]) -> String {
+    keys.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// keys of a sequence are separated by spaces, e.g. "g g"
+fn parse_key_sequence(key_sequence: &str) -> Result<Vec<KeyBinding>, String> {
+    let keys = key_sequence
+        .split_whitespace()
+        .map(KeyBinding::try_from)
+        .collect::<Result<Vec<KeyBinding>, String>>()?;
+    if keys.is_empty() {
+        return Err(String::from("Empty key sequence in keymap"));
+    }
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_sequence() {
+        let keymap = Keymap::default();
+        let g_key = KeyBinding::char('g');
+
+        assert_eq!(keymap.lookup(&[g_key]), KeyLookup::Pending);
+        assert_eq!(
+            keymap.lookup(&[g_key, g_key]),
+            KeyLookup::Matched(Action::CursorTop)
+        );
+        assert_eq!(
+            keymap.lookup(&[g_key, KeyBinding::char('q')]),
+            KeyLookup::Unbound
+        );
+        assert_eq!(
+            keymap.lookup(&[KeyBinding::char('j')]),
+            KeyLookup::Matched(Action::CursorDown)
+        );
+    }
+
+    #[test]
+    fn test_shift_ignored_for_chars() {
+        let keymap = Keymap::default();
+        let key = KeyBinding::from(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT));
+
+        assert_eq!(
+            keymap.lookup(&[key]),
+            KeyLookup::Matched(Action::CursorBottom)
+        );
+    }
+
+    #[test]
+    fn test_parse_key_sequence() {
+        assert_eq!(
+            parse_key_sequence("ctrl+d").unwrap(),
+            vec![KeyBinding::new(KeyCode::Char('d'), KeyModifiers::CONTROL)]
+        );
+        assert_eq!(
+            parse_key_sequence("g shift+g").unwrap(),
+            vec![KeyBinding::char('g'), KeyBinding::char('G')]
+        );
+        assert_eq!(
+            parse_key_sequence("alt+Left ctrl++").unwrap(),
+            vec![
+                KeyBinding::new(KeyCode::Left, KeyModifiers::ALT),
+                KeyBinding::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+            ]
+        );
+        assert_eq!(
+            parse_key_sequence("space f5").unwrap(),
+            vec![KeyBinding::char(' '), KeyBinding::plain(KeyCode::F(5))]
+        );
+        assert!(parse_key_sequence("hyper+x").is_err());
+        assert!(parse_key_sequence("enterr").is_err());
+        assert!(parse_key_sequence(" ").is_err());
+    }
+
+    #[test]
+    fn test_keymap_with_config() {
+        let keymap_config = HashMap::from([
+            (String::from("H"), String::from("toggle_hidden")),
+            (String::from("h"), String::from("unbound")),
+            (String::from("g h"), String::from("go_back")),
+        ]);
+        let keymap = Keymap::with_config(&keymap_config).unwrap();
+
+        assert_eq!(
+            keymap.lookup(&[KeyBinding::char('H')]),
+            KeyLookup::Matched(Action::ToggleHidden)
+        );
+        assert_eq!(keymap.lookup(&[KeyBinding::char('h')]), KeyLookup::Unbound);
+        assert_eq!(
+            keymap.lookup(&[KeyBinding::char('g'), KeyBinding::char('h')]),
+            KeyLookup::Matched(Action::GoBack)
+        );
+        assert_eq!(
+            keymap.lookup(&[KeyBinding::char('g'), KeyBinding::char('g')]),
+            KeyLookup::Matched(Action::CursorTop)
+        );
+
+        let keymap_config = HashMap::from([(String::from("x"), String::from("explode"))]);
+        assert!(Keymap::with_config(&keymap_config).is_err());
+    }
+
+    #[test]
+    fn test_keymap_config_conflicts() {
+        let keymap_config = HashMap::from([
+            (String::from("G"), String::from("cursor_top")),
+            (String::from("shift+g"), String::from("cursor_bottom")),
+        ]);
+        assert!(Keymap::with_config(&keymap_config).is_err());
+
+        let keymap_config = HashMap::from([(String::from("g"), String::from("go_back"))]);
+        assert_eq!(
+            Keymap::with_config(&keymap_config).err().unwrap(),
+            "\"g\" makes \"g g\" unreachable"
+        );
+
+        let keymap_config = HashMap::from([
+            (String::from("g"), String::from("go_back")),
+            (String::from("g g"), String::from("unbound")),
+            (String::from("g d"), String::from("unbound")),
+        ]);
+        let keymap = Keymap::with_config(&keymap_config).unwrap();
+        assert_eq!(
+            keymap.lookup(&[KeyBinding::char('g')]),
+            KeyLookup::Matched(Action::GoBack)
+        );
+    }
+
+    #[test]
+    fn test_action_names() {
+        for action_name in Action::names() {
+            let action = Action::from_name(action_name).unwrap();
+            assert_eq!(action.name(), action_name);
+        }
+        assert_eq!(
+            Action::from_name("half_page_down"),
+            Some(Action::HalfPageDown)
+        );
+        assert_eq!(Action::from_name("explode"), None);
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for key_sequence in [
+            "g g", "ctrl+d", "alt+left", "space", "G", "pageup", "del", "f5",
+        ] {
+            let keys = parse_key_sequence(key_sequence).unwrap();
+            let displayed: Vec<String> = keys.iter().map(|x| x.to_string()).collect();
+            assert_eq!(displayed.join(" "), key_sequence);
+        }
+    }
+
+    #[test]
+    fn test_help_entries() {
+        let keymap_config = HashMap::from([(String::from("H"), String::from("toggle_hidden"))]);
+        let keymap = Keymap::with_config(&keymap_config).unwrap();
+        let help_entries = keymap.help_entries();
+
+        assert_eq!(help_entries.len(), Action::all().count());
+        assert!(help_entries.contains(&(String::from("h, H"), Action::ToggleHidden)));
+        assert!(help_entries.contains(&(String::from("home, g g"), Action::CursorTop)));
+    }
+}
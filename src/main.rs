@@ -1,14 +1,29 @@
 use std::{env, error::Error};
 
 mod app;
+mod bookmarks;
 mod cli;
+mod columns;
+mod command;
+mod config;
 #[cfg(feature = "crossterm")]
 mod crossterm;
 mod entry;
+mod file_colors;
+mod finder;
+mod frecency;
+mod fuzzy;
+mod icons;
+mod keymap;
+mod pane;
 mod path;
+mod preview;
+mod search;
+mod sort;
 mod status_bar;
+mod tab;
+mod theme;
 mod ui;
-mod ui_styles;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let env_log = env_logger::Env::default();
@@ -0,0 +1,845 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::DirEntry,
+    path::{Path, PathBuf},
+};
+
+use ratatui::{style::Style, widgets::ListState};
+
+use crate::{
+    columns::{self, Column, LONG_LISTING_COLUMNS},
+    file_colors,
+    fuzzy::fuzzy_match,
+    icons, path,
+    preview::{Preview, PreviewKind},
+    sort::{self, SortOrder},
+};
+
+pub struct DirListState {
+    pub state: ListState,
+    pub items: Vec<DirEntry>,
+    // the color of each item, looked up once when the items are set since it needs a stat
+    pub styles: Vec<Style>,
+    // icons are off by default, so they're only looked up by `load_icons` once they're shown
+    pub icons: Vec<Option<&'static str>>,
+}
+
+impl DirListState {
+    pub fn new(items: Vec<DirEntry>) -> Self {
+        Self {
+            state: ListState::default(),
+            styles: items.iter().map(file_colors::entry_style).collect(),
+            icons: Vec::new(),
+            items,
+        }
+    }
+
+    pub fn set_items(&mut self, items: Vec<DirEntry>) {
+        self.styles = items.iter().map(file_colors::entry_style).collect();
+        self.icons.clear();
+        self.items = items;
+        self.state = ListState::default();
+    }
+
+    // only does any work the first time after the items are set
+    pub fn load_icons(&mut self) {
+        if self.icons.len() != self.items.len() {
+            self.icons = self.items.iter().map(icons::entry_icon).collect();
+        }
+    }
+}
+
+// caps how far back the history goes so a long session doesn't keep growing it
+const MAX_HISTORY_LEN: usize = 100;
+
+// a visited directory along with the entry that was selected when it was left
+pub struct HistoryEntry {
+    pub dir: PathBuf,
+    pub selected_path: Option<PathBuf>,
+}
+
+// where the cursor was in a directory, so returning to it doesn't start back at the top
+struct DirPosition {
+    selected_path: Option<PathBuf>,
+    offset: usize,
+}
+
+// each pane keeps its own browsing state so the two columns of the dual pane layout
+// can be navigated independently of each other
+pub struct Pane {
+    pub current_dir: PathBuf,
+    pub parent_dir: PathBuf,
+    pub dir_items: DirListState,
+    pub parent_items: DirListState,
+    pub preview: Preview,
+    pub show_hidden: bool,
+    pub sort_order: SortOrder,
+    pub filter: String,
+    pub back_history: Vec<HistoryEntry>,
+    pub forward_history: Vec<HistoryEntry>,
+    dir_positions: HashMap<PathBuf, DirPosition>,
+    pub tree_mode: bool,
+    pub expanded_dirs: HashSet<PathBuf>,
+    // how deep each entry of `dir_items` is nested below the current directory
+    pub tree_depths: Vec<usize>,
+    // the indent and expanded marker drawn before each entry in tree mode, worked out when the
+    // listing is loaded since the marker needs a stat
+    pub tree_prefixes: Vec<String>,
+    // the characters of each entry of `dir_items` matched by the filter, empty without a filter
+    pub filter_matches: Vec<Vec<usize>>,
+    pub long_listing: bool,
+    pub columns: Vec<Column>,
+    // the long listing's columns for each entry of `dir_items`
+    pub detail_cells: Vec<Vec<String>>,
+    // number of entries visible at once, kept up to date by the ui for paging
+    pub page_height: usize,
+}
+
+impl Pane {
+    pub fn new<T: AsRef<Path>>(init_dir: T) -> Self {
+        let mut pane = Pane {
+            current_dir: init_dir.as_ref().to_path_buf(),
+            parent_dir: path::get_parent_dir(&init_dir),
+            dir_items: DirListState::new(Vec::new()),
+            parent_items: DirListState::new(Vec::new()),
+            preview: Preview::new(),
+            show_hidden: false,
+            sort_order: SortOrder::default(),
+            filter: String::new(),
+            back_history: Vec::new(),
+            forward_history: Vec::new(),
+            dir_positions: HashMap::new(),
+            tree_mode: false,
+            expanded_dirs: HashSet::new(),
+            tree_depths: Vec::new(),
+            tree_prefixes: Vec::new(),
+            filter_matches: Vec::new(),
+            long_listing: false,
+            columns: LONG_LISTING_COLUMNS.to_vec(),
+            detail_cells: Vec::new(),
+            page_height: 0,
+        };
+        pane.refresh_dirlist();
+        pane
+    }
+
+    // the cursor stays on the same entry across a refresh as long as that entry is still listed
+    pub fn refresh_dirlist(&mut self) {
+        self.remember_position();
+        self.load_dirlist();
+    }
+
+    fn load_dirlist(&mut self) {
+        let items = path::get_dir_items(&self.current_dir, &self.show_hidden);
+        let mut items = sort::sort_dir_items(items, &self.sort_order);
+        let mut filter_matches = Vec::new();
+        if !self.filter.is_empty() {
+            (items, filter_matches) = items
+                .into_iter()
+                .filter_map(|x| {
+                    let (_, matched_indices) =
+                        fuzzy_match(&self.filter, &x.file_name().to_string_lossy())?;
+                    Some((x, matched_indices))
+                })
+                .unzip();
+        }
+        if self.tree_mode {
+            let mut tree_items = Vec::new();
+            self.expand_tree_items(items, 0, &mut tree_items);
+            let (items, tree_depths): (Vec<DirEntry>, Vec<usize>) = tree_items.into_iter().unzip();
+            // the nested entries weren't filtered, so they have nothing to highlight
+            if !filter_matches.is_empty() {
+                let mut top_level_matches = filter_matches.into_iter();
+                filter_matches = tree_depths
+                    .iter()
+                    .map(|depth| match depth {
+                        0 => top_level_matches.next().unwrap_or_default(),
+                        _ => Vec::new(),
+                    })
+                    .collect();
+            }
+            self.tree_prefixes = self.tree_prefixes(&items, &tree_depths);
+            self.dir_items.set_items(items);
+            self.tree_depths = tree_depths;
+        } else {
+            self.dir_items.set_items(items);
+            self.tree_depths.clear();
+            self.tree_prefixes.clear();
+        }
+        self.filter_matches = filter_matches;
+        self.detail_cells = if self.long_listing {
+            columns::detail_cells(&self.dir_items.items, &self.columns)
+        } else {
+            Vec::new()
+        };
+        self.restore_position();
+        self.auto_select_first();
+        self.refresh_parent_items();
+        // a file preview keeps its page or jumped to line while the same file stays selected,
+        // a directory preview is cheap to reload and its entries may have just changed
+        let selected_path = self.selected_entry().map(|x| x.path());
+        if selected_path != self.preview.path || matches!(self.preview.kind, PreviewKind::Dir(_)) {
+            self.preview = Preview::new();
+        }
+    }
+
+    // the children of expanded directories are listed right below them, only the top level is filtered
+    fn expand_tree_items(
+        &self,
+        items: Vec<DirEntry>,
+        depth: usize,
+        tree_items: &mut Vec<(DirEntry, usize)>,
+    ) {
+        for item in items {
+            let item_path = item.path();
+            tree_items.push((item, depth));
+            if self.expanded_dirs.contains(&item_path) && item_path.is_dir() {
+                let child_items = path::get_dir_items(&item_path, &self.show_hidden);
+                let child_items = sort::sort_dir_items(child_items, &self.sort_order);
+                self.expand_tree_items(child_items, depth + 1, tree_items);
+            }
+        }
+    }
+
+    // each entry is indented by its depth, with a marker showing whether a directory is expanded
+    fn tree_prefixes(&self, items: &[DirEntry], tree_depths: &[usize]) -> Vec<String> {
+        items
+            .iter()
+            .zip(tree_depths)
+            .map(|(x, depth)| {
+                let entry_path = x.path();
+                let marker = if self.is_expanded(&entry_path) {
+                    "▾ "
+                } else if entry_path.is_dir() {
+                    "▸ "
+                } else {
+                    "  "
+                };
+                format!("{}{marker}", "  ".repeat(*depth))
+            })
+            .collect()
+    }
+
+    pub fn toggle_long_listing(&mut self) {
+        self.long_listing = !self.long_listing;
+        self.refresh_dirlist();
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<Column>) {
+        self.columns = columns;
+        self.refresh_dirlist();
+    }
+
+    pub fn toggle_tree_mode(&mut self) {
+        self.tree_mode = !self.tree_mode;
+        self.refresh_dirlist();
+    }
+
+    // on a directory this expands or collapses it, on anything nested it collapses the
+    // directory the entry is listed under and moves the cursor there
+    pub fn toggle_expanded(&mut self) {
+        let Some(selected_path) = self.selected_entry().map(|x| x.path()) else {
+            return;
+        };
+
+        if selected_path.is_dir() {
+            if !self.expanded_dirs.remove(&selected_path) {
+                self.expanded_dirs.insert(selected_path);
+            }
+        } else if self.selected_depth() > 0 {
+            let parent_path = path::get_parent_dir(&selected_path);
+            self.expanded_dirs.remove(&parent_path);
+            self.refresh_dirlist();
+            self.select_path(parent_path);
+            return;
+        }
+        self.refresh_dirlist();
+    }
+
+    pub fn is_expanded<T: AsRef<Path>>(&self, dir: T) -> bool {
+        self.tree_mode && self.expanded_dirs.contains(dir.as_ref())
+    }
+
+    fn selected_depth(&self) -> usize {
+        self.dir_items
+            .state
+            .selected()
+            .and_then(|idx| self.tree_depths.get(idx))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    // in the tree, pasting goes next to the selected entry, which may be nested in a subdirectory
+    pub fn paste_dir(&self) -> PathBuf {
+        match self.selected_entry() {
+            Some(selected_entry) if self.selected_depth() > 0 => {
+                path::get_parent_dir(selected_entry.path())
+            }
+            _ => self.current_dir.clone(),
+        }
+    }
+
+    // the parent listing is only used by the miller column layout, with the current directory
+    // selected in it so it is clear where in the parent the current listing comes from
+    fn refresh_parent_items(&mut self) {
+        if self.parent_dir == self.current_dir {
+            self.parent_items.set_items(Vec::new());
+            return;
+        }
+
+        // sorted the same way as the current directory so the miller columns agree
+        let parent_items = path::get_dir_items(&self.parent_dir, &self.show_hidden);
+        self.parent_items
+            .set_items(sort::sort_dir_items(parent_items, &self.sort_order));
+        let current_dir_idx = self
+            .parent_items
+            .items
+            .iter()
+            .position(|x| x.path() == self.current_dir);
+        self.parent_items.state.select(current_dir_idx);
+    }
+
+    fn remember_position(&mut self) {
+        let dir_position = DirPosition {
+            selected_path: self.selected_entry().map(|x| x.path()),
+            offset: self.dir_items.state.offset(),
+        };
+        self.dir_positions
+            .insert(self.current_dir.clone(), dir_position);
+    }
+
+    fn restore_position(&mut self) {
+        let Some(dir_position) = self.dir_positions.get(&self.current_dir) else {
+            return;
+        };
+        let selected_idx = dir_position
+            .selected_path
+            .as_ref()
+            .and_then(|selected_path| {
+                self.dir_items
+                    .items
+                    .iter()
+                    .position(|x| &x.path() == selected_path)
+            });
+        // the list widget moves the offset back if the selection would end up off screen
+        *self.dir_items.state.offset_mut() = dir_position.offset;
+        self.dir_items.state.select(selected_idx);
+    }
+
+    // the preview is loaded lazily and only reloaded once the selection has changed
+    pub fn sync_preview(&mut self, syntax_theme: &str) {
+        let selected_path = self.selected_entry().map(|x| x.path());
+        if selected_path == self.preview.path {
+            return;
+        }
+
+        self.preview = match selected_path {
+            Some(selected_path) => Preview::load(
+                selected_path,
+                &self.show_hidden,
+                &self.sort_order,
+                syntax_theme,
+            ),
+            None => Preview::new(),
+        };
+    }
+
+    pub fn selected_entry(&self) -> Option<&DirEntry> {
+        self.dir_items
+            .state
+            .selected()
+            .and_then(|idx| self.dir_items.items.get(idx))
+    }
+
+    fn auto_select_first(&mut self) {
+        match self.dir_items.state.selected() {
+            Some(_) => (),
+            None => {
+                self.dir_items.state.select_first();
+            }
+        }
+    }
+
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.refresh_dirlist();
+    }
+
+    pub fn set_sort_order(&mut self, sort_order: SortOrder) {
+        self.sort_order = sort_order;
+        self.refresh_dirlist();
+    }
+
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.refresh_dirlist();
+    }
+
+    // `row` counts from the top of the visible part of the list, returns whether there was an entry
+    pub fn select_row(&mut self, row: usize) -> bool {
+        let selected_idx = self.dir_items.state.offset() + row;
+        if selected_idx >= self.dir_items.items.len() {
+            return false;
+        }
+        self.dir_items.state.select(Some(selected_idx));
+        true
+    }
+
+    // moves by `offset` entries, stopping at either end of the listing
+    pub fn move_cursor_by(&mut self, offset: isize) {
+        let Some(selected_idx) = self.dir_items.state.selected() else {
+            return;
+        };
+        self.move_cursor_to(selected_idx.saturating_add_signed(offset));
+    }
+
+    pub fn move_cursor_to(&mut self, selected_idx: usize) {
+        if self.dir_items.items.is_empty() {
+            return;
+        }
+        let selected_idx = selected_idx.min(self.dir_items.items.len() - 1);
+        self.dir_items.state.select(Some(selected_idx));
+    }
+
+    // before the first draw the page height isn't known yet, so a page is at least one entry
+    pub fn move_cursor_pages(&mut self, page_fraction: f32) {
+        let page_offset = (self.page_height as f32 * page_fraction) as isize;
+        let page_offset = match page_offset {
+            0 => page_fraction.signum() as isize,
+            _ => page_offset,
+        };
+        self.move_cursor_by(page_offset);
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        self.dir_items.state.select_previous();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        self.dir_items.state.select_next();
+    }
+
+    // returns whether the pane actually moved up
+    pub fn nav_up_dir(&mut self) -> bool {
+        let new_current_dirpath = self.parent_dir.clone();
+        let new_parent_dirpath = path::get_parent_dir(&new_current_dirpath);
+
+        // only navigate up if the current directory is not the root directory
+        if new_parent_dirpath != self.current_dir {
+            let prev_dirpath = self.current_dir.clone();
+            self.push_history();
+            self.set_current_dir(new_current_dirpath);
+            self.select_path(prev_dirpath);
+            return true;
+        }
+        false
+    }
+
+    // the parent is derived from the new directory rather than the current one, so this works
+    // for jumping anywhere and not only for entering a subdirectory
+    pub fn enter_dir<T: AsRef<Path>>(&mut self, selected_dir: T) {
+        self.push_history();
+        self.set_current_dir(selected_dir);
+    }
+
+    // returns whether there was anywhere to go back to
+    pub fn go_back(&mut self) -> bool {
+        match self.back_history.pop() {
+            Some(history_entry) => {
+                let current_entry = self.current_history_entry();
+                self.forward_history.push(current_entry);
+                self.restore_history_entry(history_entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // returns whether there was anywhere to go forward to
+    pub fn go_forward(&mut self) -> bool {
+        match self.forward_history.pop() {
+            Some(history_entry) => {
+                let current_entry = self.current_history_entry();
+                self.back_history.push(current_entry);
+                self.restore_history_entry(history_entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // like a browser, going somewhere new drops whatever could have been gone forward to
+    fn push_history(&mut self) {
+        let current_entry = self.current_history_entry();
+        self.back_history.push(current_entry);
+        if self.back_history.len() > MAX_HISTORY_LEN {
+            self.back_history.remove(0);
+        }
+        self.forward_history.clear();
+    }
+
+    fn current_history_entry(&self) -> HistoryEntry {
+        HistoryEntry {
+            dir: self.current_dir.clone(),
+            selected_path: self.selected_entry().map(|x| x.path()),
+        }
+    }
+
+    fn restore_history_entry(&mut self, history_entry: HistoryEntry) {
+        self.set_current_dir(history_entry.dir);
+        if let Some(selected_path) = history_entry.selected_path {
+            self.select_path(selected_path);
+        }
+    }
+
+    fn set_current_dir<T: AsRef<Path>>(&mut self, selected_dir: T) {
+        self.remember_position();
+        self.current_dir = selected_dir.as_ref().to_path_buf();
+        self.parent_dir = path::get_parent_dir(&self.current_dir);
+        self.filter.clear();
+        self.load_dirlist();
+    }
+
+    pub fn select_path<T: AsRef<Path>>(&mut self, selected_path: T) {
+        let selected_idx = self
+            .dir_items
+            .items
+            .iter()
+            .position(|x| x.path() == selected_path.as_ref());
+        if selected_idx.is_some() {
+            self.dir_items.state.select(selected_idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_enter_dir() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.enter_dir(&test_subdir);
+
+        assert_eq!(pane.current_dir, test_subdir);
+        assert_eq!(pane.parent_dir, test_dir.path());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_toggle_hidden() {
+        let test_dir = tempdir().unwrap();
+        let _test_file = fs::File::create(test_dir.path().join(".hidden_file")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.refresh_dirlist();
+        assert!(pane.selected_entry().is_none());
+
+        pane.toggle_hidden();
+
+        assert_eq!(pane.dir_items.items.len(), 1);
+        assert!(pane.selected_entry().is_some());
+        assert_eq!(
+            pane.dir_items.styles,
+            vec![file_colors::entry_style(&pane.dir_items.items[0])]
+        );
+        assert!(pane.dir_items.icons.is_empty());
+        pane.dir_items.load_icons();
+        assert_eq!(pane.dir_items.icons.len(), 1);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_parent_items_selects_current_dir() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("test_file.txt")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.enter_dir(&test_subdir);
+
+        let selected_idx = pane.parent_items.state.selected().unwrap();
+        assert_eq!(pane.parent_items.items[selected_idx].path(), test_subdir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_parent_items_sorted() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("zdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("a.txt")).unwrap();
+
+        let mut pane = Pane::new(&test_subdir);
+        pane.set_sort_order(SortOrder {
+            dirs_first: true,
+            ..SortOrder::default()
+        });
+        assert_eq!(pane.parent_items.items[0].path(), test_subdir);
+
+        pane.set_sort_order(SortOrder::default());
+        assert_eq!(pane.parent_items.items[1].path(), test_subdir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_sync_preview_follows_cursor() {
+        let test_dir = tempdir().unwrap();
+        let _first_file = fs::File::create(test_dir.path().join("a.txt")).unwrap();
+        let _second_file = fs::File::create(test_dir.path().join("b.txt")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.refresh_dirlist();
+        pane.sync_preview(&Theme::default().preview_syntax_theme);
+        assert_eq!(pane.preview.path, Some(test_dir.path().join("a.txt")));
+
+        pane.move_cursor_down();
+        pane.sync_preview(&Theme::default().preview_syntax_theme);
+        assert_eq!(pane.preview.path, Some(test_dir.path().join("b.txt")));
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_refresh_keeps_preview_position() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join("a.txt");
+        fs::write(&test_filepath, "line\n".repeat(100)).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.sync_preview(&Theme::default().preview_syntax_theme);
+        pane.preview.page_down();
+        let offset = pane.preview.offset;
+        assert!(offset > 0);
+
+        pane.toggle_hidden();
+        assert_eq!(pane.preview.path, Some(test_filepath));
+        assert_eq!(pane.preview.offset, offset);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_filter_narrows_items() {
+        let test_dir = tempdir().unwrap();
+        let _first_file = fs::File::create(test_dir.path().join("Cargo.toml")).unwrap();
+        let _second_file = fs::File::create(test_dir.path().join("README.md")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.set_filter("crgo".to_string());
+        assert_eq!(pane.dir_items.items.len(), 1);
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "Cargo.toml");
+        assert_eq!(pane.filter_matches, vec![vec![0, 2, 3, 4]]);
+
+        pane.set_filter(String::new());
+        assert_eq!(pane.dir_items.items.len(), 2);
+        assert!(pane.filter_matches.is_empty());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_enter_dir_clears_filter() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.set_filter("sub".to_string());
+        pane.enter_dir(&test_subdir);
+
+        assert!(pane.filter.is_empty());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_select_path() {
+        let test_dir = tempdir().unwrap();
+        let _first_file = fs::File::create(test_dir.path().join("a.txt")).unwrap();
+        let _second_file = fs::File::create(test_dir.path().join("b.txt")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.select_path(test_dir.path().join("b.txt"));
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "b.txt");
+
+        // unknown paths leave the selection alone
+        pane.select_path(test_dir.path().join("c.txt"));
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "b.txt");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_history_back_forward() {
+        let test_dir = tempdir().unwrap();
+        let first_subdir = test_dir.path().join("first");
+        let second_subdir = first_subdir.join("second");
+        fs::create_dir_all(&second_subdir).unwrap();
+        let _test_file = fs::File::create(first_subdir.join("test_file.txt")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.enter_dir(&first_subdir);
+        pane.select_path(first_subdir.join("test_file.txt"));
+        pane.enter_dir(&second_subdir);
+
+        assert!(pane.go_back());
+        assert_eq!(pane.current_dir, first_subdir);
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "test_file.txt");
+
+        assert!(pane.go_back());
+        assert_eq!(pane.current_dir, test_dir.path());
+        assert!(!pane.go_back());
+
+        assert!(pane.go_forward());
+        assert!(pane.go_forward());
+        assert_eq!(pane.current_dir, second_subdir);
+        assert!(!pane.go_forward());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_history_new_dir_clears_forward() {
+        let test_dir = tempdir().unwrap();
+        let first_subdir = test_dir.path().join("first");
+        let second_subdir = test_dir.path().join("second");
+        fs::create_dir(&first_subdir).unwrap();
+        fs::create_dir(&second_subdir).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.enter_dir(&first_subdir);
+        pane.go_back();
+        pane.enter_dir(&second_subdir);
+
+        assert!(pane.forward_history.is_empty());
+        assert!(!pane.go_forward());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_nav_up_selects_previous_dir() {
+        let test_dir = tempdir().unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("a_file.txt")).unwrap();
+        let test_subdir = test_dir.path().join("z_subdir");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let mut pane = Pane::new(&test_subdir);
+        assert!(pane.nav_up_dir());
+        assert_eq!(pane.selected_entry().unwrap().path(), test_subdir);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_position_remembered_per_dir() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        for filename in ["a.txt", "b.txt", "c.txt"] {
+            let _test_file = fs::File::create(test_subdir.join(filename)).unwrap();
+        }
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.enter_dir(&test_subdir);
+        pane.select_path(test_subdir.join("c.txt"));
+        pane.nav_up_dir();
+        pane.enter_dir(&test_subdir);
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "c.txt");
+
+        // a refresh keeps the cursor on the same entry even when entries before it are gone
+        fs::remove_file(test_subdir.join("a.txt")).unwrap();
+        pane.refresh_dirlist();
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "c.txt");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_tree_expand_collapse() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let test_filepath = test_subdir.join("nested.txt");
+        let _test_file = fs::File::create(&test_filepath).unwrap();
+        let _other_file = fs::File::create(test_dir.path().join("z_file.txt")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.toggle_tree_mode();
+        pane.select_path(&test_subdir);
+        pane.toggle_expanded();
+
+        assert_eq!(pane.dir_items.items.len(), 3);
+        assert_eq!(pane.tree_depths, vec![0, 1, 0]);
+        assert_eq!(pane.tree_prefixes, vec!["▾ ", "    ", "  "]);
+        assert!(pane.is_expanded(&test_subdir));
+
+        pane.move_cursor_down();
+        assert_eq!(pane.selected_entry().unwrap().path(), test_filepath);
+        assert_eq!(pane.paste_dir(), test_subdir);
+
+        // collapsing from a nested entry moves the cursor back to its directory
+        pane.toggle_expanded();
+        assert_eq!(pane.dir_items.items.len(), 2);
+        assert_eq!(pane.selected_entry().unwrap().path(), test_subdir);
+        assert_eq!(pane.paste_dir(), test_dir.path());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_select_row() {
+        let test_dir = tempdir().unwrap();
+        for filename in ["a.txt", "b.txt", "c.txt"] {
+            let _test_file = fs::File::create(test_dir.path().join(filename)).unwrap();
+        }
+
+        let mut pane = Pane::new(test_dir.path());
+        *pane.dir_items.state.offset_mut() = 1;
+        assert!(pane.select_row(1));
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "c.txt");
+        assert!(!pane.select_row(2));
+        assert_eq!(pane.selected_entry().unwrap().file_name(), "c.txt");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_move_cursor_by_and_pages() {
+        let test_dir = tempdir().unwrap();
+        for idx in 0..10 {
+            let _test_file = fs::File::create(test_dir.path().join(format!("{idx}.txt"))).unwrap();
+        }
+
+        let mut pane = Pane::new(test_dir.path());
+        pane.move_cursor_by(3);
+        assert_eq!(pane.dir_items.state.selected(), Some(3));
+        pane.move_cursor_by(-5);
+        assert_eq!(pane.dir_items.state.selected(), Some(0));
+        pane.move_cursor_to(20);
+        assert_eq!(pane.dir_items.state.selected(), Some(9));
+
+        pane.page_height = 4;
+        pane.move_cursor_pages(-0.5);
+        assert_eq!(pane.dir_items.state.selected(), Some(7));
+        pane.move_cursor_pages(-1.0);
+        assert_eq!(pane.dir_items.state.selected(), Some(3));
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_long_listing_cells() {
+        let test_dir = tempdir().unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("test_file.txt")).unwrap();
+        let _other_file = fs::File::create(test_dir.path().join("other_file.txt")).unwrap();
+
+        let mut pane = Pane::new(test_dir.path());
+        assert!(pane.detail_cells.is_empty());
+
+        pane.toggle_long_listing();
+        assert_eq!(pane.detail_cells.len(), 2);
+        assert_eq!(pane.detail_cells[0].len(), LONG_LISTING_COLUMNS.len());
+
+        pane.set_columns(vec![Column::Name, Column::Inode]);
+        assert_eq!(pane.detail_cells[0].len(), 2);
+        test_dir.close().unwrap();
+    }
+}
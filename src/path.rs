@@ -4,6 +4,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use log::debug;
+
 pub fn get_parent_dir<T: AsRef<Path>>(selected_dir: T) -> PathBuf {
     selected_dir
         .as_ref()
@@ -13,10 +15,13 @@ pub fn get_parent_dir<T: AsRef<Path>>(selected_dir: T) -> PathBuf {
 }
 
 pub fn get_dir_items<T: AsRef<Path>>(selected_dir: T, show_hidden: &bool) -> Vec<DirEntry> {
-    let mut item_paths: Vec<_> = fs::read_dir(selected_dir)
-        .unwrap()
-        .map(|x| x.unwrap())
-        .collect();
+    let mut item_paths: Vec<_> = match fs::read_dir(selected_dir) {
+        Ok(read_dir) => read_dir.filter_map(|x| x.ok()).collect(),
+        Err(e) => {
+            debug!("Unable to read directory: {e:?}");
+            Vec::new()
+        }
+    };
     if !show_hidden {
         item_paths.retain(|x| !x.file_name().into_string().unwrap().starts_with("."));
     }
@@ -25,6 +30,117 @@ pub fn get_dir_items<T: AsRef<Path>>(selected_dir: T, show_hidden: &bool) -> Vec
     item_paths
 }
 
+// visits every entry below `selected_dir` depth first, symlinked directories are not followed
+// to avoid walking in circles. The walk stops as soon as `visit` returns false.
+pub fn walk_dir<T: AsRef<Path>>(
+    selected_dir: T,
+    show_hidden: &bool,
+    visit: &mut dyn FnMut(&DirEntry) -> bool,
+) -> bool {
+    for entry in get_dir_items(selected_dir, show_hidden) {
+        if !visit(&entry) {
+            return false;
+        }
+        let is_dir = entry.file_type().is_ok_and(|x| x.is_dir());
+        if is_dir && !walk_dir(entry.path(), show_hidden, visit) {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn get_file_len<T: AsRef<Path>>(filepath: T) -> u64 {
+    filepath.as_ref().metadata().map_or(0, |x| x.len())
+}
+
+// expands a leading `~` and any `$VAR` or `${VAR}` in the path, relative paths are taken to be
+// relative to `base_dir`
+pub fn expand_path<T: AsRef<Path>>(input: &str, base_dir: T) -> Result<PathBuf, String> {
+    let mut expanded = String::new();
+    let mut chars = input.chars().peekable();
+
+    if input == "~" || input.starts_with("~/") {
+        let home_dir = get_home_dir().ok_or("Unable to find the home directory")?;
+        expanded.push_str(&home_dir.to_string_lossy());
+        chars.next();
+    }
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+
+        let mut var_name = String::new();
+        if chars.next_if_eq(&'{').is_some() {
+            for c in chars.by_ref() {
+                if c == '}' {
+                    break;
+                }
+                var_name.push(c);
+            }
+        } else {
+            while let Some(c) = chars.next_if(|x| x.is_ascii_alphanumeric() || *x == '_') {
+                var_name.push(c);
+            }
+        }
+
+        match env::var(&var_name) {
+            Ok(value) => expanded.push_str(&value),
+            Err(_) => return Err(format!("Unknown variable: ${var_name}")),
+        }
+    }
+
+    Ok(base_dir.as_ref().join(expanded))
+}
+
+// returns the directories that could complete the last component of `input`, keeping the
+// rest of the input as typed so completing doesn't expand `~` or variables
+pub fn complete_dir<T: AsRef<Path>>(input: &str, base_dir: T, show_hidden: &bool) -> Vec<String> {
+    let (typed_dir, partial_name) = match input.rfind('/') {
+        Some(idx) => input.split_at(idx + 1),
+        None => ("", input),
+    };
+    let Ok(search_dir) = expand_path(typed_dir, base_dir) else {
+        return Vec::new();
+    };
+
+    // hidden directories are only offered when hidden files are shown or asked for explicitly
+    let show_hidden = *show_hidden || partial_name.starts_with('.');
+    get_dir_items(search_dir, &show_hidden)
+        .iter()
+        .filter(|x| x.path().is_dir())
+        .map(|x| x.file_name().to_string_lossy().to_string())
+        .filter(|x| x.starts_with(partial_name))
+        .map(|x| format!("{typed_dir}{x}/"))
+        .collect()
+}
+
+// `env::home_dir` is deprecated before rust 1.86, and $HOME is all it reads on unix anyway
+pub fn get_home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+}
+
+// follows the XDG base directory spec, falling back to ~/.local/share when it isn't set
+pub fn get_data_dir() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| get_home_dir().map(|x| x.join(".local").join("share")))
+        .map(|x| x.join(env!("CARGO_PKG_NAME")))
+}
+
+// like the data dir, falling back to ~/.config when XDG_CONFIG_HOME isn't set
+pub fn get_config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|x| !x.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| get_home_dir().map(|x| x.join(".config")))
+        .map(|x| x.join(env!("CARGO_PKG_NAME")))
+}
+
 pub fn get_current_dirpath() -> PathBuf {
     env::current_dir().expect("Current Directory does not exists or invalid permissions")
 }
@@ -32,6 +148,7 @@ pub fn get_current_dirpath() -> PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::tempdir;
 
     #[test]
     fn test_get_current_dirpath() {
@@ -69,4 +186,84 @@ mod tests {
         // .git/, .gitignore, .vscode/
         assert_eq!(9, result.len());
     }
+
+    #[test]
+    fn test_walk_dir() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let _test_file = fs::File::create(test_subdir.join("test_file.txt")).unwrap();
+        let _hidden_file = fs::File::create(test_subdir.join(".hidden_file")).unwrap();
+
+        let mut result = Vec::new();
+        walk_dir(test_dir.path(), &false, &mut |x| {
+            result.push(x.path());
+            true
+        });
+
+        assert_eq!(
+            result,
+            vec![test_subdir.clone(), test_subdir.join("test_file.txt")]
+        );
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_expand_path_relative() {
+        let result = expand_path("src", "/tmp").unwrap();
+
+        assert_eq!(result, PathBuf::from("/tmp/src"));
+    }
+
+    #[test]
+    fn test_expand_path_absolute() {
+        let result = expand_path("/usr/bin", "/tmp").unwrap();
+
+        assert_eq!(result, PathBuf::from("/usr/bin"));
+    }
+
+    #[test]
+    fn test_expand_path_home() {
+        let result = expand_path("~/Documents", "/tmp").unwrap();
+
+        assert_eq!(result, get_home_dir().unwrap().join("Documents"));
+    }
+
+    #[test]
+    fn test_expand_path_vars() {
+        let home_dir = env::var("HOME").unwrap();
+
+        assert_eq!(
+            expand_path("$HOME/a", "/tmp").unwrap(),
+            PathBuf::from(&home_dir).join("a")
+        );
+        assert_eq!(
+            expand_path("${HOME}a", "/tmp").unwrap(),
+            PathBuf::from(format!("{home_dir}a"))
+        );
+        assert!(expand_path("$FNDESK_UNSET_VARIABLE", "/tmp").is_err());
+    }
+
+    #[test]
+    fn test_complete_dir() {
+        let test_dir = tempdir().unwrap();
+        fs::create_dir_all(test_dir.path().join("src/module")).unwrap();
+        fs::create_dir(test_dir.path().join("scripts")).unwrap();
+        fs::create_dir(test_dir.path().join(".secret")).unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("start.txt")).unwrap();
+
+        assert_eq!(
+            complete_dir("s", test_dir.path(), &false),
+            vec!["scripts/", "src/"]
+        );
+        assert_eq!(
+            complete_dir("src/m", test_dir.path(), &false),
+            vec!["src/module/"]
+        );
+        assert_eq!(
+            complete_dir(".s", test_dir.path(), &false),
+            vec![".secret/"]
+        );
+        test_dir.close().unwrap();
+    }
 }
@@ -0,0 +1,475 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use log::debug;
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{self, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+use crate::{
+    pane::DirListState,
+    path,
+    sort::{self, SortOrder},
+};
+
+// only the beginning of a file is ever shown, so there is no need to read all of it
+const PREVIEW_MAX_BYTES: u64 = 16 * 1024;
+const PREVIEW_MAX_LINES: usize = 200;
+const PREVIEW_TAB_WIDTH: &str = "    ";
+const PREVIEW_PAGE_LINES: usize = 32;
+const HEX_BYTES_PER_LINE: usize = 16;
+const HEX_PAGE_BYTES: u64 = (HEX_BYTES_PER_LINE * PREVIEW_PAGE_LINES) as u64;
+
+// loading the syntax definitions takes a noticeable amount of time, so it is only done
+// the first time a file is previewed
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+pub enum PreviewKind {
+    Empty,
+    Dir(DirListState),
+    Text(Vec<Line<'static>>),
+    Hex(Vec<Line<'static>>),
+}
+
+// `offset` is the first line shown for text previews and the first byte for hex previews
+pub struct Preview {
+    pub path: Option<PathBuf>,
+    pub kind: PreviewKind,
+    pub offset: u64,
+}
+
+impl Preview {
+    pub fn new() -> Self {
+        Preview {
+            path: None,
+            kind: PreviewKind::Empty,
+            offset: 0,
+        }
+    }
+
+    pub fn load<T: AsRef<Path>>(
+        selected_path: T,
+        show_hidden: &bool,
+        sort_order: &SortOrder,
+        syntax_theme: &str,
+    ) -> Self {
+        Preview::load_at_line(selected_path, 0, show_hidden, sort_order, syntax_theme)
+    }
+
+    // text previews start from `first_line` so positions past the read limit can be shown,
+    // any other kind of preview ignores it
+    pub fn load_at_line<T: AsRef<Path>>(
+        selected_path: T,
+        first_line: usize,
+        show_hidden: &bool,
+        sort_order: &SortOrder,
+        syntax_theme: &str,
+    ) -> Self {
+        let selected_path = selected_path.as_ref();
+        let kind = if selected_path.is_dir() {
+            // listed the same way as the pane will list it once the directory is entered
+            let dir_items = path::get_dir_items(selected_path, show_hidden);
+            let mut dir_items = DirListState::new(sort::sort_dir_items(dir_items, sort_order));
+            dir_items.state.select_first();
+            PreviewKind::Dir(dir_items)
+        } else if selected_path.is_file() {
+            let mut contents = read_bytes(selected_path, 0, PREVIEW_MAX_BYTES);
+            if is_text(&contents) {
+                if first_line > 0 {
+                    contents = read_bytes_from_line(selected_path, first_line, PREVIEW_MAX_BYTES);
+                }
+                let contents = String::from_utf8_lossy(&contents);
+                PreviewKind::Text(highlight_text(selected_path, &contents, syntax_theme))
+            } else {
+                PreviewKind::Hex(hex_dump(
+                    &contents[..contents.len().min(HEX_PAGE_BYTES as usize)],
+                    0,
+                ))
+            }
+        } else {
+            PreviewKind::Empty
+        };
+
+        Preview {
+            path: Some(selected_path.to_path_buf()),
+            kind,
+            offset: 0,
+        }
+    }
+
+    pub fn page_down(&mut self) {
+        match &self.kind {
+            PreviewKind::Text(lines) => {
+                let last_line = lines.len().saturating_sub(1) as u64;
+                self.offset = (self.offset + PREVIEW_PAGE_LINES as u64).min(last_line);
+            }
+            PreviewKind::Hex(_) => {
+                let file_len = self.path.as_ref().map_or(0, path::get_file_len);
+                if self.offset + HEX_PAGE_BYTES < file_len {
+                    self.load_hex_page(self.offset + HEX_PAGE_BYTES);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn page_up(&mut self) {
+        match &self.kind {
+            PreviewKind::Text(_) => {
+                self.offset = self.offset.saturating_sub(PREVIEW_PAGE_LINES as u64);
+            }
+            PreviewKind::Hex(_) => {
+                self.load_hex_page(self.offset.saturating_sub(HEX_PAGE_BYTES));
+            }
+            _ => {}
+        }
+    }
+
+    fn load_hex_page(&mut self, offset: u64) {
+        if let Some(filepath) = &self.path {
+            let contents = read_bytes(filepath, offset, HEX_PAGE_BYTES);
+            self.kind = PreviewKind::Hex(hex_dump(&contents, offset));
+            self.offset = offset;
+        }
+    }
+}
+
+fn read_bytes<T: AsRef<Path>>(filepath: T, offset: u64, max_bytes: u64) -> Vec<u8> {
+    let mut contents = Vec::new();
+    match File::open(filepath) {
+        Ok(mut file) => {
+            let read_result = file
+                .seek(SeekFrom::Start(offset))
+                .and_then(|_| file.take(max_bytes).read_to_end(&mut contents));
+            if let Err(e) = read_result {
+                debug!("Unable to read file for preview: {e:?}");
+            }
+        }
+        Err(e) => debug!("Unable to open file for preview: {e:?}"),
+    }
+
+    contents
+}
+
+fn read_bytes_from_line<T: AsRef<Path>>(filepath: T, first_line: usize, max_bytes: u64) -> Vec<u8> {
+    let mut contents = Vec::new();
+    match File::open(filepath) {
+        Ok(file) => {
+            let mut reader = BufReader::new(file);
+            let mut skipped_line = Vec::new();
+            for _ in 0..first_line {
+                skipped_line.clear();
+                match reader.read_until(b'\n', &mut skipped_line) {
+                    Ok(0) => break,
+                    Ok(_) => (),
+                    Err(e) => {
+                        debug!("Unable to read file for preview: {e:?}");
+                        return contents;
+                    }
+                }
+            }
+            if let Err(e) = reader.take(max_bytes).read_to_end(&mut contents) {
+                debug!("Unable to read file for preview: {e:?}");
+            }
+        }
+        Err(e) => debug!("Unable to open file for preview: {e:?}"),
+    }
+
+    contents
+}
+
+// a file is treated as text when it has no null bytes and is valid utf-8, ignoring a
+// multi-byte character that might have been cut off at the end of the read
+fn is_text(contents: &[u8]) -> bool {
+    if contents.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(contents) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+// formats the bytes in the same layout as `hexdump -C`, the offset, hex and ascii columns each
+// get a span so the ui can style them
+fn hex_dump(contents: &[u8], offset: u64) -> Vec<Line<'static>> {
+    contents
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(idx, chunk)| {
+            let line_offset = offset + (idx * HEX_BYTES_PER_LINE) as u64;
+            let mut hex_column = String::new();
+            for byte_idx in 0..HEX_BYTES_PER_LINE {
+                if byte_idx == HEX_BYTES_PER_LINE / 2 {
+                    hex_column.push(' ');
+                }
+                match chunk.get(byte_idx) {
+                    Some(byte) => hex_column.push_str(&format!("{byte:02x} ")),
+                    None => hex_column.push_str("   "),
+                }
+            }
+            let ascii_column: String = chunk
+                .iter()
+                .map(|x| {
+                    if x.is_ascii_graphic() || *x == b' ' {
+                        *x as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            Line::from(vec![
+                Span::raw(format!("{line_offset:08x}  ")),
+                Span::raw(hex_column),
+                Span::raw(format!(" |{ascii_column}|")),
+            ])
+        })
+        .collect()
+}
+
+// `syntax_theme` is one of syntect's default themes, see `is_syntax_theme`
+fn highlight_text<T: AsRef<Path>>(
+    filepath: T,
+    contents: &str,
+    syntax_theme: &str,
+) -> Vec<Line<'static>> {
+    let syntax = find_syntax(filepath, contents);
+    let Some(theme) = THEME_SET.themes.get(syntax_theme) else {
+        debug!("Unknown syntax theme {syntax_theme:?}");
+        return LinesWithEndings::from(contents)
+            .take(PREVIEW_MAX_LINES)
+            .map(|line| Line::raw(clean_preview_text(line)))
+            .collect();
+    };
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    LinesWithEndings::from(contents)
+        .take(PREVIEW_MAX_LINES)
+        .map(|line| match highlighter.highlight_line(line, &SYNTAX_SET) {
+            Ok(ranges) => Line::from_iter(
+                ranges
+                    .into_iter()
+                    .map(|(style, text)| Span::styled(clean_preview_text(text), to_style(style))),
+            ),
+            Err(e) => {
+                debug!("Unable to highlight line: {e:?}");
+                Line::raw(clean_preview_text(line))
+            }
+        })
+        .collect()
+}
+
+// the syntax is picked from the file extension first, then the file name for files such as
+// Makefile, and lastly the first line to pick up shebangs
+fn find_syntax<T: AsRef<Path>>(filepath: T, contents: &str) -> &'static SyntaxReference {
+    let filepath = filepath.as_ref();
+    let extension = filepath.extension().and_then(|x| x.to_str());
+    let filename = filepath.file_name().and_then(|x| x.to_str());
+    let first_line = contents.lines().next().unwrap_or_default();
+
+    extension
+        .and_then(|x| SYNTAX_SET.find_syntax_by_extension(x))
+        .or_else(|| filename.and_then(|x| SYNTAX_SET.find_syntax_by_extension(x)))
+        .or_else(|| SYNTAX_SET.find_syntax_by_first_line(first_line))
+        .unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text())
+}
+
+fn clean_preview_text(text: &str) -> String {
+    text.trim_end_matches(['\n', '\r'])
+        .replace('\t', PREVIEW_TAB_WIDTH)
+}
+
+pub fn is_syntax_theme(name: &str) -> bool {
+    THEME_SET.themes.contains_key(name)
+}
+
+// only the foreground is used, so the preview keeps the background of the rest of the app
+fn to_style(style: highlighting::Style) -> Style {
+    let highlighting::Color { r, g, b, .. } = style.foreground;
+    Style::new().fg(Color::Rgb(r, g, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    const TEST_SYNTAX_THEME: &str = "base16-ocean.dark";
+
+    #[test]
+    fn test_preview_dir() {
+        let test_dir = tempdir().unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("test_file.txt")).unwrap();
+
+        let preview = Preview::load(
+            test_dir.path(),
+            &false,
+            &SortOrder::default(),
+            TEST_SYNTAX_THEME,
+        );
+
+        match preview.kind {
+            PreviewKind::Dir(dir_items) => assert_eq!(dir_items.items.len(), 1),
+            _ => panic!("Expected a directory preview"),
+        }
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_preview_dir_sorted() {
+        let test_dir = tempdir().unwrap();
+        let _test_file = fs::File::create(test_dir.path().join("a_file.txt")).unwrap();
+        fs::create_dir(test_dir.path().join("b_dir")).unwrap();
+
+        let sort_order = SortOrder {
+            dirs_first: true,
+            ..SortOrder::default()
+        };
+        let preview = Preview::load(test_dir.path(), &false, &sort_order, TEST_SYNTAX_THEME);
+
+        match preview.kind {
+            PreviewKind::Dir(dir_items) => {
+                let names: Vec<_> = dir_items.items.iter().map(|x| x.file_name()).collect();
+                assert_eq!(names, vec!["b_dir", "a_file.txt"]);
+            }
+            _ => panic!("Expected a directory preview"),
+        }
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_preview_text_file() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join("test_file.txt");
+        fs::write(&test_filepath, "first line\nsecond line\n").unwrap();
+
+        let preview = Preview::load(
+            &test_filepath,
+            &false,
+            &SortOrder::default(),
+            TEST_SYNTAX_THEME,
+        );
+
+        match preview.kind {
+            PreviewKind::Text(lines) => {
+                let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+                assert_eq!(lines, vec!["first line", "second line"]);
+            }
+            _ => panic!("Expected a text preview"),
+        }
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_find_syntax_by_extension() {
+        let syntax = find_syntax("main.rs", "fn main() {}");
+
+        assert_eq!(syntax.name, "Rust");
+    }
+
+    #[test]
+    fn test_find_syntax_by_shebang() {
+        let syntax = find_syntax("run", "#!/usr/bin/env python3\nprint('hello')");
+
+        assert_eq!(syntax.name, "Python");
+    }
+
+    #[test]
+    fn test_find_syntax_plain_text() {
+        let syntax = find_syntax("notes", "nothing special in here");
+
+        assert_eq!(syntax.name, "Plain Text");
+    }
+
+    #[test]
+    fn test_is_text() {
+        assert!(is_text("plain text".as_bytes()));
+        assert!(is_text("café".as_bytes()));
+        // a multi-byte character cut off by the read limit
+        assert!(is_text(&"café".as_bytes()[..4]));
+        assert!(!is_text(&[0x7f, 0x45, 0x4c, 0x46, 0x00]));
+        assert!(!is_text(&[0xff, 0xfe, 0x41]));
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let lines = hex_dump("ABCDEFGHIJKLMNOPQ".as_bytes(), 0x20);
+        let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                "00000020  41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50  |ABCDEFGHIJKLMNOP|",
+                "00000030  51                                                |Q|",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preview_binary_paging() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join("test_file.bin");
+        fs::write(&test_filepath, vec![0u8; HEX_PAGE_BYTES as usize + 1]).unwrap();
+
+        let mut preview = Preview::load(
+            &test_filepath,
+            &false,
+            &SortOrder::default(),
+            TEST_SYNTAX_THEME,
+        );
+        assert!(matches!(preview.kind, PreviewKind::Hex(_)));
+
+        preview.page_down();
+        assert_eq!(preview.offset, HEX_PAGE_BYTES);
+        match &preview.kind {
+            PreviewKind::Hex(lines) => assert_eq!(lines.len(), 1),
+            _ => panic!("Expected a hex preview"),
+        }
+
+        // already on the last page
+        preview.page_down();
+        assert_eq!(preview.offset, HEX_PAGE_BYTES);
+
+        preview.page_up();
+        assert_eq!(preview.offset, 0);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_preview_at_line() {
+        let test_dir = tempdir().unwrap();
+        let test_filepath = test_dir.path().join("test_file.txt");
+        fs::write(&test_filepath, "first line\nsecond line\nthird line\n").unwrap();
+
+        let preview = Preview::load_at_line(
+            &test_filepath,
+            1,
+            &false,
+            &SortOrder::default(),
+            TEST_SYNTAX_THEME,
+        );
+
+        match preview.kind {
+            PreviewKind::Text(lines) => {
+                let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+                assert_eq!(lines, vec!["second line", "third line"]);
+            }
+            _ => panic!("Expected a text preview"),
+        }
+        test_dir.close().unwrap();
+    }
+}
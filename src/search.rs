@@ -0,0 +1,331 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, Sender, TryRecvError},
+        Arc,
+    },
+    thread,
+};
+
+use log::debug;
+use ratatui::widgets::ListState;
+use regex::Regex;
+
+use crate::path;
+
+// files larger than this are most likely logs or data dumps and would stall the search
+const MAX_SEARCH_FILE_BYTES: u64 = 10 * 1024 * 1024;
+const MAX_SEARCH_RESULTS: usize = 10_000;
+// same heuristic as grep, a null byte near the start of the file means it is binary
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+const SEARCH_TAB_WIDTH: &str = "    ";
+
+pub struct SearchResult {
+    pub path: PathBuf,
+    pub line_number: usize,
+    pub line: String,
+}
+
+// like the finder, the tree is searched on a separate thread with the results streamed back.
+// Setting `cancelled` or dropping the search stops the thread at the next line it reads.
+pub struct ContentSearch {
+    pub root: PathBuf,
+    pub query: String,
+    pub use_regex: bool,
+    pub results: Vec<SearchResult>,
+    pub state: ListState,
+    pub is_searching: bool,
+    pub error: Option<String>,
+    show_hidden: bool,
+    receiver: Option<Receiver<SearchResult>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ContentSearch {
+    pub fn new<T: AsRef<Path>>(root: T, show_hidden: bool) -> Self {
+        ContentSearch {
+            root: root.as_ref().to_path_buf(),
+            query: String::new(),
+            use_regex: false,
+            results: Vec::new(),
+            state: ListState::default(),
+            is_searching: false,
+            error: None,
+            show_hidden,
+            receiver: None,
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn start(&mut self) {
+        self.cancel();
+        self.results.clear();
+        self.state = ListState::default();
+        self.error = None;
+        if self.query.is_empty() {
+            return;
+        }
+
+        let pattern = if self.use_regex {
+            Regex::new(&self.query)
+        } else {
+            Regex::new(&regex::escape(&self.query))
+        };
+        let pattern = match pattern {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let root = self.root.clone();
+        let show_hidden = self.show_hidden;
+        let thread_cancelled = Arc::clone(&cancelled);
+        thread::spawn(move || {
+            path::walk_dir(&root, &show_hidden, &mut |entry| {
+                if thread_cancelled.load(Ordering::Relaxed) {
+                    return false;
+                }
+                if !entry.file_type().is_ok_and(|x| x.is_file()) {
+                    return true;
+                }
+                search_file(entry.path(), &pattern, &sender, &thread_cancelled)
+            });
+        });
+
+        self.receiver = Some(receiver);
+        self.cancelled = cancelled;
+        self.is_searching = true;
+    }
+
+    pub fn cancel(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.receiver = None;
+        self.is_searching = false;
+    }
+
+    // results from an earlier query would be misleading, so they are dropped until the
+    // search is started again
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.cancel();
+        self.results.clear();
+        self.state = ListState::default();
+        self.error = None;
+    }
+
+    pub fn toggle_regex(&mut self) {
+        self.use_regex = !self.use_regex;
+        self.set_query(self.query.clone());
+    }
+
+    // takes in whatever the search has found since the last poll
+    // returns whether any results came in or the search finished
+    pub fn poll(&mut self) -> bool {
+        let Some(receiver) = &self.receiver else {
+            return false;
+        };
+        let result_count = self.results.len();
+
+        loop {
+            match receiver.try_recv() {
+                Ok(result) => self.results.push(result),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    self.is_searching = false;
+                    break;
+                }
+            }
+        }
+
+        if self.results.len() >= MAX_SEARCH_RESULTS {
+            self.results.truncate(MAX_SEARCH_RESULTS);
+            self.cancel();
+        }
+        if self.state.selected().is_none() && !self.results.is_empty() {
+            self.state.select_first();
+        }
+        result_count != self.results.len() || !self.is_searching
+    }
+
+    pub fn move_cursor_up(&mut self) {
+        self.state.select_previous();
+    }
+
+    pub fn move_cursor_down(&mut self) {
+        self.state.select_next();
+    }
+
+    pub fn selected_result(&self) -> Option<&SearchResult> {
+        self.state.selected().and_then(|idx| self.results.get(idx))
+    }
+}
+
+impl Drop for ContentSearch {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+// returns false once the search should stop, either from being cancelled or from the
+// receiving end of the channel being dropped
+fn search_file<T: AsRef<Path>>(
+    filepath: T,
+    pattern: &Regex,
+    sender: &Sender<SearchResult>,
+    cancelled: &AtomicBool,
+) -> bool {
+    let filepath = filepath.as_ref();
+    if path::get_file_len(filepath) > MAX_SEARCH_FILE_BYTES {
+        return true;
+    }
+
+    let mut reader = match File::open(filepath) {
+        Ok(file) => BufReader::new(file),
+        Err(e) => {
+            debug!("Unable to open file for searching: {e:?}");
+            return true;
+        }
+    };
+    match reader.fill_buf() {
+        Ok(contents) if contents[..contents.len().min(BINARY_SNIFF_BYTES)].contains(&0) => {
+            return true;
+        }
+        Ok(_) => (),
+        Err(e) => {
+            debug!("Unable to read file for searching: {e:?}");
+            return true;
+        }
+    }
+
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return false;
+        }
+
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => return true,
+            Ok(_) => line_number += 1,
+            Err(e) => {
+                debug!("Unable to read file for searching: {e:?}");
+                return true;
+            }
+        }
+
+        let line_text = String::from_utf8_lossy(&line);
+        if pattern.is_match(&line_text) {
+            let result = SearchResult {
+                path: filepath.to_path_buf(),
+                line_number,
+                line: line_text.trim_end().replace('\t', SEARCH_TAB_WIDTH),
+            };
+            if sender.send(result).is_err() {
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn wait_for_search(search: &mut ContentSearch) {
+        while search.is_searching {
+            search.poll();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn test_search_literal() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("subdir");
+        fs::create_dir(&test_subdir).unwrap();
+        let test_filepath = test_subdir.join("test_file.txt");
+        fs::write(&test_filepath, "first line\nneedle.* here\nthird line\n").unwrap();
+
+        let mut search = ContentSearch::new(test_dir.path(), false);
+        search.set_query("needle.*".to_string());
+        search.start();
+        wait_for_search(&mut search);
+
+        assert_eq!(search.results.len(), 1);
+        let result = search.selected_result().unwrap();
+        assert_eq!(result.path, test_filepath);
+        assert_eq!(result.line_number, 2);
+        assert_eq!(result.line, "needle.* here");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_regex() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("test_file.txt"), "ab12\nab\ncd34\n").unwrap();
+
+        let mut search = ContentSearch::new(test_dir.path(), false);
+        search.set_query(r"[a-z]+\d+".to_string());
+        search.toggle_regex();
+        search.start();
+        wait_for_search(&mut search);
+
+        let lines: Vec<&str> = search.results.iter().map(|x| x.line.as_str()).collect();
+        assert_eq!(lines, vec!["ab12", "cd34"]);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_invalid_regex() {
+        let test_dir = tempdir().unwrap();
+
+        let mut search = ContentSearch::new(test_dir.path(), false);
+        search.set_query("(".to_string());
+        search.toggle_regex();
+        search.start();
+
+        assert!(search.error.is_some());
+        assert!(!search.is_searching);
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_skips_binary() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("test_file.bin"), b"needle\0").unwrap();
+
+        let mut search = ContentSearch::new(test_dir.path(), false);
+        search.set_query("needle".to_string());
+        search.start();
+        wait_for_search(&mut search);
+
+        assert!(search.results.is_empty());
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_search_cancel() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("test_file.txt"), "needle\n").unwrap();
+
+        let mut search = ContentSearch::new(test_dir.path(), false);
+        search.set_query("needle".to_string());
+        search.start();
+        search.cancel();
+        search.poll();
+
+        assert!(!search.is_searching);
+        test_dir.close().unwrap();
+    }
+}
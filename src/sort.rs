@@ -0,0 +1,270 @@
+use std::{
+    cmp::Ordering,
+    ffi::OsString,
+    fmt,
+    fs::{DirEntry, FileType},
+    time::SystemTime,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SortMode {
+    Name,
+    Natural,
+    Size,
+    Modified,
+    Extension,
+    Type,
+}
+
+const SORT_MODES: [(SortMode, &str); 6] = [
+    (SortMode::Name, "name"),
+    (SortMode::Natural, "natural"),
+    (SortMode::Size, "size"),
+    (SortMode::Modified, "modified"),
+    (SortMode::Extension, "extension"),
+    (SortMode::Type, "type"),
+];
+
+impl SortMode {
+    pub fn from_name(name: &str) -> Option<SortMode> {
+        SORT_MODES
+            .iter()
+            .find(|(_, mode_name)| *mode_name == name)
+            .map(|(sort_mode, _)| *sort_mode)
+    }
+
+    pub fn name(&self) -> &'static str {
+        SORT_MODES
+            .iter()
+            .find(|(sort_mode, _)| sort_mode == self)
+            .map_or("", |(_, mode_name)| mode_name)
+    }
+
+    pub fn next(&self) -> SortMode {
+        let mode_idx = SORT_MODES
+            .iter()
+            .position(|(sort_mode, _)| sort_mode == self)
+            .unwrap_or(0);
+        SORT_MODES[(mode_idx + 1) % SORT_MODES.len()].0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SortOrder {
+    pub mode: SortMode,
+    pub dirs_first: bool,
+    pub reverse: bool,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder {
+            mode: SortMode::Name,
+            dirs_first: false,
+            reverse: false,
+        }
+    }
+}
+
+// shown in the title bar, e.g. "size, dirs first, reversed"
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mode.name())?;
+        if self.dirs_first {
+            write!(f, ", dirs first")?;
+        }
+        if self.reverse {
+            write!(f, ", reversed")?;
+        }
+        Ok(())
+    }
+}
+
+// everything an entry can be sorted on, read once per entry so comparisons don't stat again
+struct SortKey {
+    name: OsString,
+    natural_name: String,
+    is_dir: bool,
+    type_rank: u8,
+    size: u64,
+    modified: Option<SystemTime>,
+    extension: Option<OsString>,
+}
+
+impl SortKey {
+    fn new(entry: &DirEntry) -> Self {
+        let entry_path = entry.path();
+        let metadata = entry.metadata().ok();
+        // a symlink to a directory is grouped with the directories, only symlinks need a second
+        // stat to find out what they point to
+        let is_dir = match &metadata {
+            Some(metadata) if metadata.is_symlink() => entry_path.is_dir(),
+            Some(metadata) => metadata.is_dir(),
+            None => false,
+        };
+        SortKey {
+            name: entry.file_name(),
+            natural_name: entry.file_name().to_string_lossy().to_string(),
+            is_dir,
+            type_rank: entry.file_type().map_or(u8::MAX, |x| type_rank(&x)),
+            size: metadata.as_ref().map_or(0, |x| x.len()),
+            modified: metadata.and_then(|x| x.modified().ok()),
+            extension: entry_path.extension().map(|x| x.to_ascii_lowercase()),
+        }
+    }
+}
+
+fn type_rank(file_type: &FileType) -> u8 {
+    if file_type.is_dir() {
+        0
+    } else if file_type.is_symlink() {
+        1
+    } else if file_type.is_file() {
+        2
+    } else {
+        3
+    }
+}
+
+// size and modified put the largest and newest first, like `ls -S` and `ls -t`, everything
+// else ties on the name
+pub fn sort_dir_items(items: Vec<DirEntry>, sort_order: &SortOrder) -> Vec<DirEntry> {
+    let mut keyed_items: Vec<(SortKey, DirEntry)> =
+        items.into_iter().map(|x| (SortKey::new(&x), x)).collect();
+
+    keyed_items.sort_by(|(a, _), (b, _)| {
+        let mode_ordering = match sort_order.mode {
+            SortMode::Name => Ordering::Equal,
+            SortMode::Natural => natord::compare(&a.natural_name, &b.natural_name),
+            SortMode::Size => b.size.cmp(&a.size),
+            SortMode::Modified => b.modified.cmp(&a.modified),
+            SortMode::Extension => a.extension.cmp(&b.extension),
+            SortMode::Type => a.type_rank.cmp(&b.type_rank),
+        };
+        let ordering = mode_ordering.then_with(|| a.name.cmp(&b.name));
+        let ordering = if sort_order.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+
+        // directories stay first even when the rest is reversed
+        if sort_order.dirs_first {
+            b.is_dir.cmp(&a.is_dir).then(ordering)
+        } else {
+            ordering
+        }
+    });
+    keyed_items.into_iter().map(|(_, x)| x).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn sorted_names<T: AsRef<std::path::Path>>(dir: T, sort_order: &SortOrder) -> Vec<String> {
+        let items = path::get_dir_items(dir, &false);
+        sort_dir_items(items, sort_order)
+            .iter()
+            .map(|x| x.file_name().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_modes() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("file10.txt"), "0123456789").unwrap();
+        fs::write(test_dir.path().join("file9.rs"), "01234").unwrap();
+        fs::write(test_dir.path().join("file1.md"), "").unwrap();
+        fs::create_dir(test_dir.path().join("zdir")).unwrap();
+
+        let mut sort_order = SortOrder::default();
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order),
+            vec!["file1.md", "file10.txt", "file9.rs", "zdir"]
+        );
+
+        sort_order.mode = SortMode::Natural;
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order),
+            vec!["file1.md", "file9.rs", "file10.txt", "zdir"]
+        );
+
+        sort_order.mode = SortMode::Extension;
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order),
+            vec!["zdir", "file1.md", "file9.rs", "file10.txt"]
+        );
+
+        sort_order.mode = SortMode::Type;
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order)[0],
+            String::from("zdir")
+        );
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_sort_size_dirs_first_reverse() {
+        let test_dir = tempdir().unwrap();
+        fs::write(test_dir.path().join("big.txt"), [0; 10000]).unwrap();
+        fs::write(test_dir.path().join("small.txt"), "0").unwrap();
+        fs::create_dir(test_dir.path().join("dir")).unwrap();
+
+        let mut sort_order = SortOrder {
+            mode: SortMode::Size,
+            dirs_first: true,
+            reverse: false,
+        };
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order),
+            vec!["dir", "big.txt", "small.txt"]
+        );
+
+        sort_order.reverse = true;
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order),
+            vec!["dir", "small.txt", "big.txt"]
+        );
+        assert_eq!(sort_order.to_string(), "size, dirs first, reversed");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_sort_dirs_first_symlink() {
+        let test_dir = tempdir().unwrap();
+        fs::create_dir(test_dir.path().join("z_dir")).unwrap();
+        fs::write(test_dir.path().join("a_file.txt"), "").unwrap();
+        std::os::unix::fs::symlink(
+            test_dir.path().join("z_dir"),
+            test_dir.path().join("b_dir_link"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(
+            test_dir.path().join("a_file.txt"),
+            test_dir.path().join("c_file_link"),
+        )
+        .unwrap();
+
+        let sort_order = SortOrder {
+            dirs_first: true,
+            ..SortOrder::default()
+        };
+        assert_eq!(
+            sorted_names(test_dir.path(), &sort_order),
+            vec!["b_dir_link", "z_dir", "a_file.txt", "c_file_link"]
+        );
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_sort_mode_names() {
+        assert_eq!(SortMode::from_name("modified"), Some(SortMode::Modified));
+        assert_eq!(SortMode::from_name("random"), None);
+        assert_eq!(SortMode::Type.next(), SortMode::Name);
+        assert_eq!(SortMode::Name.next().name(), "natural");
+    }
+}
@@ -1,31 +1,24 @@
-use std::os::linux::fs::MetadataExt;
-use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
-use human_bytes::human_bytes;
 use log::debug;
-use users::{get_group_by_gid, get_user_by_uid};
+use users::UsersCache;
+
+use crate::columns::{format_group, format_owner, format_permissions, format_size};
 
 pub fn status_string<T: AsRef<Path>>(current_entry: T) -> String {
     let file_attributes = current_entry.as_ref().metadata();
     match file_attributes {
         Ok(attributes) => {
+            let users_cache = UsersCache::new();
             let mut status_string = String::from("");
-            let entry_permissions = attributes.permissions();
-            let user_id = attributes.st_uid();
-            let group_id = attributes.st_gid();
-            let filesize_bytes = human_bytes(attributes.st_size() as f64);
-            let _entry_last_modified = attributes.modified();
-
-            let permission_string = unix_mode::to_string(entry_permissions.mode());
 
-            status_string.push_str(&permission_string);
+            status_string.push_str(&format_permissions(&attributes));
             status_string.push_str("  ");
-            status_string.push_str(&get_username_from_id(user_id));
+            status_string.push_str(&format_owner(&attributes, &users_cache));
             status_string.push_str("  ");
-            status_string.push_str(&get_groupname_from_id(group_id));
+            status_string.push_str(&format_group(&attributes, &users_cache));
             status_string.push_str("  ");
-            status_string.push_str(&filesize_bytes.to_string());
+            status_string.push_str(&format_size(&attributes));
 
             status_string
         }
@@ -36,26 +29,12 @@ pub fn status_string<T: AsRef<Path>>(current_entry: T) -> String {
     }
 }
 
-fn get_username_from_id(user_id: u32) -> String {
-    let user = get_user_by_uid(user_id);
-    match user {
-        Some(user) => user.name().to_string_lossy().to_string(),
-        None => user_id.to_string(),
-    }
-}
-
-fn get_groupname_from_id(group_id: u32) -> String {
-    let group = get_group_by_gid(group_id);
-    match group {
-        Some(group) => group.name().to_string_lossy().to_string(),
-        None => group_id.to_string(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
+    use std::os::linux::fs::MetadataExt;
+    use users::get_user_by_uid;
 
     use tempfile::tempdir;
 
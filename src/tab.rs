@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use crate::pane::Pane;
+
+// a tab holds its own pair of panes, so switching tabs brings back both sides of the
+// dual pane layout as they were left
+pub struct Tab {
+    pub panes: [Pane; 2],
+    pub active_pane: usize,
+}
+
+impl Tab {
+    pub fn new<T: AsRef<Path>>(init_dir: T) -> Self {
+        Tab {
+            panes: [Pane::new(&init_dir), Pane::new(&init_dir)],
+            active_pane: 0,
+        }
+    }
+
+    pub fn pane(&self) -> &Pane {
+        &self.panes[self.active_pane]
+    }
+
+    pub fn pane_mut(&mut self) -> &mut Pane {
+        &mut self.panes[self.active_pane]
+    }
+
+    pub fn switch_panes(&mut self) {
+        self.active_pane = (self.active_pane + 1) % self.panes.len();
+    }
+
+    // the tab bar only has room for the name of the directory, not its full path
+    pub fn label(&self) -> String {
+        let current_dir = &self.pane().current_dir;
+        match current_dir.file_name() {
+            Some(dir_name) => dir_name.to_string_lossy().to_string(),
+            None => current_dir.to_string_lossy().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_label() {
+        let test_dir = tempdir().unwrap();
+        let test_subdir = test_dir.path().join("project");
+        fs::create_dir(&test_subdir).unwrap();
+
+        let tab = Tab::new(&test_subdir);
+        assert_eq!(tab.label(), "project");
+
+        let root_tab = Tab::new("/");
+        assert_eq!(root_tab.label(), "/");
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_switch_panes() {
+        let test_dir = tempdir().unwrap();
+        let mut tab = Tab::new(test_dir.path());
+        tab.switch_panes();
+        assert_eq!(tab.active_pane, 1);
+        tab.switch_panes();
+        assert_eq!(tab.active_pane, 0);
+        test_dir.close().unwrap();
+    }
+}
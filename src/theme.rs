@@ -0,0 +1,336 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::{path, preview};
+
+const THEMES_DIRNAME: &str = "themes";
+pub const DEFAULT_THEME: &str = "dark";
+const BUILTIN_THEMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub current_dir: Style,
+    pub sort_order: Style,
+    pub selected_entry: Style,
+    pub inactive_selected_entry: Style,
+    pub filter_match: Style,
+    pub search_result_path: Style,
+    pub search_error: Style,
+    pub bookmark_name: Style,
+    pub help_keys: Style,
+    pub help_action_name: Style,
+    pub hex_offset: Style,
+    pub hex_ascii: Style,
+    pub status_bar: Style,
+    pub active_pane_border: Style,
+    pub inactive_pane_border: Style,
+    pub active_tab: Style,
+    pub inactive_tab: Style,
+    // one of syntect's default themes, for the syntax highlighting of text previews
+    pub preview_syntax_theme: String,
+}
+
+// a theme file sets any of the styles by name, the rest come from `base`, e.g.
+//
+//   base = "light"
+//   preview_syntax_theme = "Solarized (light)"
+//   [selected_entry]
+//   fg = "black"
+//   bg = "#a0c0ff"
+//   modifiers = ["bold"]
+#[derive(Deserialize)]
+struct ThemeFile {
+    base: Option<String>,
+    preview_syntax_theme: Option<String>,
+    #[serde(flatten)]
+    styles: HashMap<String, StyleDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StyleDef {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    #[serde(default)]
+    modifiers: Vec<String>,
+}
+
+impl StyleDef {
+    fn to_style(&self) -> Result<Style, String> {
+        let mut style = Style::new();
+        if let Some(fg) = self.fg {
+            style = style.fg(fg);
+        }
+        if let Some(bg) = self.bg {
+            style = style.bg(bg);
+        }
+        for modifier_name in &self.modifiers {
+            let modifier = Modifier::from_name(&modifier_name.to_uppercase())
+                .ok_or_else(|| format!("Unknown modifier {modifier_name:?}"))?;
+            style = style.add_modifier(modifier);
+        }
+        Ok(style)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            current_dir: Style::new().fg(Color::Magenta),
+            sort_order: Style::new().fg(Color::DarkGray),
+            selected_entry: Style::new().bg(Color::Cyan),
+            inactive_selected_entry: Style::new().bg(Color::DarkGray),
+            filter_match: Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            search_result_path: Style::new().fg(Color::Magenta),
+            search_error: Style::new().fg(Color::Red),
+            bookmark_name: Style::new().fg(Color::Yellow),
+            help_keys: Style::new().fg(Color::Yellow),
+            help_action_name: Style::new().fg(Color::DarkGray),
+            hex_offset: Style::new().fg(Color::DarkGray),
+            hex_ascii: Style::new().fg(Color::Yellow),
+            status_bar: Style::new().bg(Color::DarkGray).fg(Color::White),
+            active_pane_border: Style::new().fg(Color::Cyan),
+            inactive_pane_border: Style::new().fg(Color::DarkGray),
+            active_tab: Style::new().bg(Color::Cyan).fg(Color::Black),
+            inactive_tab: Style::new().bg(Color::DarkGray).fg(Color::White),
+            preview_syntax_theme: String::from("base16-ocean.dark"),
+        }
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            current_dir: Style::new().fg(Color::Blue),
+            sort_order: Style::new().fg(Color::DarkGray),
+            selected_entry: Style::new().bg(Color::LightBlue).fg(Color::Black),
+            inactive_selected_entry: Style::new().bg(Color::Gray).fg(Color::Black),
+            filter_match: Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            search_result_path: Style::new().fg(Color::Blue),
+            search_error: Style::new().fg(Color::Red),
+            bookmark_name: Style::new().fg(Color::Blue),
+            help_keys: Style::new().fg(Color::Blue),
+            help_action_name: Style::new().fg(Color::DarkGray),
+            hex_offset: Style::new().fg(Color::DarkGray),
+            hex_ascii: Style::new().fg(Color::Blue),
+            status_bar: Style::new().bg(Color::Gray).fg(Color::Black),
+            active_pane_border: Style::new().fg(Color::Blue),
+            inactive_pane_border: Style::new().fg(Color::Gray),
+            active_tab: Style::new().bg(Color::Blue).fg(Color::White),
+            inactive_tab: Style::new().bg(Color::Gray).fg(Color::Black),
+            preview_syntax_theme: String::from("InspiredGitHub"),
+        }
+    }
+
+    // only black, white and the bright colors, with bold where a color alone could be missed
+    pub fn high_contrast() -> Self {
+        Theme {
+            current_dir: Style::new().fg(Color::White).add_modifier(Modifier::BOLD),
+            sort_order: Style::new().fg(Color::White),
+            selected_entry: Style::new()
+                .bg(Color::LightYellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+            inactive_selected_entry: Style::new().bg(Color::White).fg(Color::Black),
+            filter_match: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            search_result_path: Style::new().fg(Color::LightCyan),
+            search_error: Style::new()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            bookmark_name: Style::new().fg(Color::LightYellow),
+            help_keys: Style::new()
+                .fg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            help_action_name: Style::new().fg(Color::White),
+            hex_offset: Style::new().fg(Color::White),
+            hex_ascii: Style::new().fg(Color::LightYellow),
+            status_bar: Style::new().bg(Color::White).fg(Color::Black),
+            active_pane_border: Style::new().fg(Color::LightYellow),
+            inactive_pane_border: Style::new().fg(Color::White),
+            active_tab: Style::new()
+                .bg(Color::LightYellow)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+            inactive_tab: Style::new().bg(Color::White).fg(Color::Black),
+            preview_syntax_theme: String::from("base16-eighties.dark"),
+        }
+    }
+
+    fn builtin(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    pub fn load_default(name: &str) -> Result<Self, String> {
+        Theme::load(name, themes_dir())
+    }
+
+    // a theme file in the themes directory takes precedence over a built-in theme of the same name
+    pub fn load(name: &str, themes_dir: Option<PathBuf>) -> Result<Self, String> {
+        if let Some(themes_dir) = themes_dir {
+            match fs::read_to_string(themes_dir.join(format!("{name}.toml"))) {
+                Ok(contents) => return Theme::from_toml(&contents),
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Unable to read theme {name:?}, {e}")),
+            }
+        }
+        Theme::builtin(name).ok_or_else(|| format!("Unknown theme {name:?}"))
+    }
+
+    fn from_toml(contents: &str) -> Result<Self, String> {
+        let theme_file: ThemeFile =
+            toml::from_str(contents).map_err(|e| format!("Invalid theme, {}", e.message()))?;
+        let base_name = theme_file.base.as_deref().unwrap_or(DEFAULT_THEME);
+        let mut theme =
+            Theme::builtin(base_name).ok_or_else(|| format!("Unknown base theme {base_name:?}"))?;
+        if let Some(syntax_theme) = theme_file.preview_syntax_theme {
+            if !preview::is_syntax_theme(&syntax_theme) {
+                return Err(format!("Unknown preview syntax theme {syntax_theme:?}"));
+            }
+            theme.preview_syntax_theme = syntax_theme;
+        }
+
+        for (style_name, style_def) in &theme_file.styles {
+            let style = theme
+                .style_mut(style_name)
+                .ok_or_else(|| format!("Unknown style {style_name:?}"))?;
+            *style = style_def.to_style()?;
+        }
+        Ok(theme)
+    }
+
+    fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        match name {
+            "current_dir" => Some(&mut self.current_dir),
+            "sort_order" => Some(&mut self.sort_order),
+            "selected_entry" => Some(&mut self.selected_entry),
+            "inactive_selected_entry" => Some(&mut self.inactive_selected_entry),
+            "filter_match" => Some(&mut self.filter_match),
+            "search_result_path" => Some(&mut self.search_result_path),
+            "search_error" => Some(&mut self.search_error),
+            "bookmark_name" => Some(&mut self.bookmark_name),
+            "help_keys" => Some(&mut self.help_keys),
+            "help_action_name" => Some(&mut self.help_action_name),
+            "hex_offset" => Some(&mut self.hex_offset),
+            "hex_ascii" => Some(&mut self.hex_ascii),
+            "status_bar" => Some(&mut self.status_bar),
+            "active_pane_border" => Some(&mut self.active_pane_border),
+            "inactive_pane_border" => Some(&mut self.inactive_pane_border),
+            "active_tab" => Some(&mut self.active_tab),
+            "inactive_tab" => Some(&mut self.inactive_tab),
+            _ => None,
+        }
+    }
+}
+
+pub fn themes_dir() -> Option<PathBuf> {
+    path::get_config_dir().map(|x| x.join(THEMES_DIRNAME))
+}
+
+// every theme name that can be loaded, for completion
+pub fn theme_names<T: AsRef<Path>>(themes_dir: Option<T>) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|x| x.to_string()).collect();
+    if let Some(themes_dir) = themes_dir {
+        names.extend(
+            path::get_dir_items(themes_dir, &false)
+                .iter()
+                .map(|x| x.path())
+                .filter(|x| x.extension().is_some_and(|x| x == "toml"))
+                .filter_map(|x| Some(x.file_stem()?.to_string_lossy().to_string())),
+        );
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_builtin() {
+        for theme_name in BUILTIN_THEMES {
+            let theme = Theme::builtin(theme_name).unwrap();
+            assert!(preview::is_syntax_theme(&theme.preview_syntax_theme));
+        }
+        assert_eq!(Theme::load("light", None).unwrap(), Theme::light());
+        assert!(Theme::load("sepia", None).is_err());
+    }
+
+    #[test]
+    fn test_load_theme_file() {
+        let test_dir = tempdir().unwrap();
+        fs::write(
+            test_dir.path().join("mine.toml"),
+            "base = \"light\"\npreview_syntax_theme = \"Solarized (light)\"\n[selected_entry]\nfg = \"black\"\nbg = \"#a0c0ff\"\nmodifiers = [\"bold\"]\n",
+        )
+        .unwrap();
+
+        let theme = Theme::load("mine", Some(test_dir.path().to_path_buf())).unwrap();
+        assert_eq!(
+            theme.selected_entry,
+            Style::new()
+                .fg(Color::Black)
+                .bg(Color::Rgb(0xa0, 0xc0, 0xff))
+                .add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(theme.status_bar, Theme::light().status_bar);
+        assert_eq!(theme.preview_syntax_theme, "Solarized (light)");
+        assert_eq!(
+            theme_names(Some(test_dir.path())),
+            vec!["dark", "high-contrast", "light", "mine"]
+        );
+        test_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_load_invalid_theme_file() {
+        let test_dir = tempdir().unwrap();
+        let themes_dir = Some(test_dir.path().to_path_buf());
+        fs::write(
+            test_dir.path().join("a.toml"),
+            "[status_line]\nfg = \"red\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("b.toml"),
+            "[status_bar]\nfg = \"reddish\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("c.toml"),
+            "[status_bar]\nmodifiers = [\"sparkly\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("d.toml"),
+            "preview_syntax_theme = \"sepia\"\n",
+        )
+        .unwrap();
+
+        assert!(Theme::load("a", themes_dir.clone()).is_err());
+        assert!(Theme::load("b", themes_dir.clone()).is_err());
+        assert!(Theme::load("c", themes_dir.clone()).is_err());
+        assert!(Theme::load("d", themes_dir).is_err());
+        test_dir.close().unwrap();
+    }
+}
@@ -1,36 +1,27 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout},
+    layout::{Constraint, Direction, Flex, Layout, Rect},
     style::Style,
-    text::Text,
-    widgets::{Block, List, ListItem, Paragraph},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Cell, Clear, List, ListItem, Paragraph, Row, Table, TableState},
     Frame,
 };
 
 use crate::{
-    app::App,
-    ui_styles::{CURRENT_DIR_STYLE, ROUNDED_BLOCK, SELECTED_ENTRY_STYLE, STATUS_BAR_STYLE},
+    app::{App, AppState, PaneLayout},
+    bookmarks::Bookmarks,
+    columns::{self, Column},
+    finder::Finder,
+    frecency::Frecency,
+    pane::{DirListState, Pane},
+    preview::{Preview, PreviewKind},
+    search::ContentSearch,
+    theme::Theme,
 };
 
-pub fn draw(frame: &mut Frame, app: &mut App) {
-    // This is a potential UI usage for two column panes, to be implemented in the future, maybe
-    // let vertical_block = Layout::vertical([
-    //     Constraint::Length(1),
-    //     Constraint::Min(0),
-    //     Constraint::Length(1),
-    // ]);
-
-    // let [title_area, main_area, status_area] = vertical_block.areas(frame.area());
-    // let horizontal_block = Layout::horizontal([Constraint::Fill(1); 2]);
-    // let [left_area, right_area] = horizontal_block.areas(main_area);
-
-    // let title_str = app.current_dir.clone();
-    // let item_list = handle_listing_dir_items(&app.current_dir_items);
-
-    // frame.render_widget(Block::new().title(title_str), title_area);
-    // frame.render_widget(Block::new().title("Status Placeholder"), status_area);
-    // frame.render_widget(Block::bordered(), left_area);
-    // frame.render_widget(Block::bordered(), right_area);
+const FINDER_MAX_SHOWN: usize = 1000;
+const ROUNDED_BLOCK: Block = Block::bordered().border_type(BorderType::Rounded);
 
+pub fn draw(frame: &mut Frame, app: &mut App) {
     let rect_sections = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -41,25 +32,558 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         .split(frame.area());
 
     let title_block = Block::default().style(Style::default());
-    let current_dir_path = app.current_dir.clone().into_os_string().into_string();
-    let title = Paragraph::new(Text::styled(current_dir_path.unwrap(), CURRENT_DIR_STYLE))
-        .block(title_block);
-
-    let item_list: Vec<ListItem> = app
-        .dir_items
-        .items
-        .iter()
-        .map(|x| ListItem::new(x.file_name().into_string().unwrap()))
-        .collect();
+    let current_dir_path = app
+        .pane()
+        .current_dir
+        .clone()
+        .into_os_string()
+        .into_string();
+    let mut title_line = tab_bar(app);
+    let title_path_x = rect_sections[0].x + title_line.width() as u16;
+    title_line.push_span(Span::styled(
+        current_dir_path.unwrap(),
+        app.theme.current_dir,
+    ));
+    title_line.push_span(Span::styled(
+        format!("  [sort: {}]", app.pane().sort_order),
+        app.theme.sort_order,
+    ));
+    let title = Paragraph::new(title_line).block(title_block);
 
-    let dir_items_list = List::new(item_list)
-        .highlight_style(SELECTED_ENTRY_STYLE)
-        .block(ROUNDED_BLOCK);
+    app.click_areas.title_path = Rect {
+        x: title_path_x,
+        width: rect_sections[0].right().saturating_sub(title_path_x),
+        ..rect_sections[0]
+    };
+    app.click_areas.panes = [None; 2];
+    let active_pane = app.tab().active_pane;
 
     let status_contents = Paragraph::new(app.status_text.clone());
-    let status_bar = Paragraph::left_aligned(status_contents).style(STATUS_BAR_STYLE);
+    let status_bar = Paragraph::left_aligned(status_contents).style(app.theme.status_bar);
 
     frame.render_widget(title, rect_sections[0]);
-    frame.render_stateful_widget(dir_items_list, rect_sections[1], &mut app.dir_items.state);
+    let theme = &app.theme;
+    let show_icons = app.show_icons;
+    let tab = &mut app.tabs[app.active_tab];
+    match app.pane_layout {
+        PaneLayout::Single if app.show_preview => {
+            let pane_sections =
+                Layout::horizontal([Constraint::Fill(1); 2]).split(rect_sections[1]);
+            app.click_areas.panes[active_pane] = Some(pane_sections[0]);
+            let pane = tab.pane_mut();
+            draw_pane(frame, pane, theme, show_icons, pane_sections[0], None);
+            pane.sync_preview(&theme.preview_syntax_theme);
+            draw_preview(
+                frame,
+                &mut pane.preview,
+                theme,
+                show_icons,
+                pane_sections[1],
+            );
+        }
+        PaneLayout::Single => {
+            app.click_areas.panes[active_pane] = Some(rect_sections[1]);
+            draw_pane(
+                frame,
+                tab.pane_mut(),
+                theme,
+                show_icons,
+                rect_sections[1],
+                None,
+            );
+        }
+        PaneLayout::Miller => {
+            let pane_area =
+                draw_miller_columns(frame, tab.pane_mut(), theme, show_icons, rect_sections[1]);
+            app.click_areas.panes[active_pane] = Some(pane_area);
+        }
+        PaneLayout::Dual => {
+            let pane_sections =
+                Layout::horizontal([Constraint::Fill(1); 2]).split(rect_sections[1]);
+            let show_preview = app.show_preview;
+            for (idx, pane) in tab.panes.iter_mut().enumerate() {
+                // the preview takes the place of the inactive pane, like a quick view panel
+                if idx == active_pane {
+                    draw_pane(
+                        frame,
+                        pane,
+                        theme,
+                        show_icons,
+                        pane_sections[idx],
+                        Some(true),
+                    );
+                } else if !show_preview {
+                    draw_pane(
+                        frame,
+                        pane,
+                        theme,
+                        show_icons,
+                        pane_sections[idx],
+                        Some(false),
+                    );
+                } else {
+                    continue;
+                }
+                app.click_areas.panes[idx] = Some(pane_sections[idx]);
+            }
+            if show_preview {
+                let pane = tab.pane_mut();
+                pane.sync_preview(&theme.preview_syntax_theme);
+                draw_preview(
+                    frame,
+                    &mut pane.preview,
+                    theme,
+                    show_icons,
+                    pane_sections[1 - active_pane],
+                );
+            }
+        }
+    }
     frame.render_widget(status_bar, rect_sections[2]);
+
+    if let Some(finder) = &mut app.finder {
+        draw_finder(frame, finder, &app.theme);
+    }
+    if let Some(search) = &mut app.content_search {
+        draw_content_search(frame, search, &app.theme);
+    }
+    if app.app_state == AppState::Bookmarks {
+        draw_bookmarks(frame, &mut app.bookmarks, &app.theme);
+    }
+    if app.app_state == AppState::Jumping {
+        draw_jump(frame, &mut app.frecency, &app.theme);
+    }
+    if app.app_state == AppState::Help {
+        draw_help(frame, app);
+    }
+}
+
+// the tab bar is left out while there is only the one tab, to keep the title row uncluttered
+fn tab_bar(app: &App) -> Line<'static> {
+    let theme = &app.theme;
+    let mut tab_line = Line::default();
+    if app.tabs.len() == 1 {
+        return tab_line;
+    }
+
+    for (idx, tab) in app.tabs.iter().enumerate() {
+        let tab_style = if idx == app.active_tab {
+            theme.active_tab
+        } else {
+            theme.inactive_tab
+        };
+        tab_line.push_span(Span::styled(
+            format!(" {}:{} ", idx + 1, tab.label()),
+            tab_style,
+        ));
+    }
+    tab_line.push_span(Span::raw(" "));
+    tab_line
+}
+
+fn draw_finder(frame: &mut Frame, finder: &mut Finder, theme: &Theme) {
+    let area = popup_area(frame.area());
+    let walk_status = if finder.is_busy() {
+        " (searching...)"
+    } else {
+        ""
+    };
+    let title = format!(
+        "Find: {} [{}/{}]{}",
+        finder.query,
+        finder.matches.len(),
+        finder.candidates.len(),
+        walk_status
+    );
+
+    // only the best matches are worth building list items for
+    let item_list: Vec<ListItem> = finder
+        .matches
+        .iter()
+        .take(FINDER_MAX_SHOWN)
+        .map(|x| {
+            let candidate = &finder.candidates[x.candidate_idx];
+            ListItem::new(highlight_matches(candidate, &x.matched_indices, theme))
+        })
+        .collect();
+    let finder_list = List::new(item_list)
+        .highlight_style(theme.selected_entry)
+        .block(ROUNDED_BLOCK.title(title));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(finder_list, area, &mut finder.state);
+}
+
+fn draw_content_search(frame: &mut Frame, search: &mut ContentSearch, theme: &Theme) {
+    let area = popup_area(frame.area());
+    let search_mode = if search.use_regex { "regex" } else { "literal" };
+    let search_status = if search.is_searching {
+        " (searching...)"
+    } else {
+        ""
+    };
+    let title = format!(
+        "Search ({search_mode}, ctrl+r to toggle): {} [{}]{}",
+        search.query,
+        search.results.len(),
+        search_status
+    );
+
+    let item_list: Vec<ListItem> = match &search.error {
+        Some(error) => vec![ListItem::new(Text::styled(
+            error.clone(),
+            theme.search_error,
+        ))],
+        None => search
+            .results
+            .iter()
+            .map(|x| {
+                let relative_path = x.path.strip_prefix(&search.root).unwrap_or(&x.path);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{}:{}:", relative_path.to_string_lossy(), x.line_number),
+                        theme.search_result_path,
+                    ),
+                    Span::raw(format!(" {}", x.line)),
+                ]))
+            })
+            .collect(),
+    };
+    let search_list = List::new(item_list)
+        .highlight_style(theme.selected_entry)
+        .block(ROUNDED_BLOCK.title(title));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(search_list, area, &mut search.state);
+}
+
+fn draw_bookmarks(frame: &mut Frame, bookmarks: &mut Bookmarks, theme: &Theme) {
+    let area = popup_area(frame.area());
+    let item_list: Vec<ListItem> = bookmarks
+        .entries
+        .iter()
+        .map(|x| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{}  ", x.name), theme.bookmark_name),
+                Span::raw(x.path.to_string_lossy().to_string()),
+            ]))
+        })
+        .collect();
+    let bookmarks_list = List::new(item_list)
+        .highlight_style(theme.selected_entry)
+        .block(ROUNDED_BLOCK.title("Bookmarks (enter or name to jump, del to remove)"));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(bookmarks_list, area, &mut bookmarks.state);
+}
+
+fn draw_jump(frame: &mut Frame, frecency: &mut Frecency, theme: &Theme) {
+    let area = popup_area(frame.area());
+    let title = format!("Jump to: {} [{}]", frecency.query, frecency.matches.len());
+
+    let item_list: Vec<ListItem> = frecency
+        .matches
+        .iter()
+        .map(|x| {
+            let entry_path = frecency.entries[x.entry_idx].path.to_string_lossy();
+            ListItem::new(highlight_matches(&entry_path, &x.matched_indices, theme))
+        })
+        .collect();
+    let jump_list = List::new(item_list)
+        .highlight_style(theme.selected_entry)
+        .block(ROUNDED_BLOCK.title(title));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(jump_list, area, &mut frecency.state);
+}
+
+// built from the keymap itself, so it shows the user's own bindings
+fn draw_help(frame: &mut Frame, app: &mut App) {
+    let theme = &app.theme;
+    let area = popup_area(frame.area());
+    let help_entries = app.keymap.help_entries();
+    let keys_width = help_entries
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let item_list: Vec<ListItem> = help_entries
+        .into_iter()
+        .map(|(keys, action)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{keys:<keys_width$}  "), theme.help_keys),
+                Span::raw(action.description()),
+                Span::styled(format!("  ({})", action.name()), theme.help_action_name),
+            ]))
+        })
+        .collect();
+    let help_list = List::new(item_list)
+        .highlight_style(theme.selected_entry)
+        .block(ROUNDED_BLOCK.title("Key bindings (esc to close)"));
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(help_list, area, &mut app.help_state);
+}
+
+fn popup_area(area: Rect) -> Rect {
+    let [popup_area] = Layout::vertical([Constraint::Percentage(70)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup_area] = Layout::horizontal([Constraint::Percentage(80)])
+        .flex(Flex::Center)
+        .areas(popup_area);
+    popup_area
+}
+
+// `is_active` is only set when more than one pane is visible, a lone pane is drawn as is
+fn draw_pane(
+    frame: &mut Frame,
+    pane: &mut Pane,
+    theme: &Theme,
+    show_icons: bool,
+    area: Rect,
+    is_active: Option<bool>,
+) {
+    pane.page_height = area.height.saturating_sub(2) as usize;
+    let item_lines = dir_item_lines(
+        &mut pane.dir_items,
+        theme,
+        show_icons,
+        &pane.filter_matches,
+        &pane.tree_prefixes,
+    );
+
+    let (highlight_style, block) = match is_active {
+        None => (theme.selected_entry, ROUNDED_BLOCK),
+        Some(true) => (
+            theme.selected_entry,
+            ROUNDED_BLOCK
+                .title(pane.current_dir.to_string_lossy().to_string())
+                .border_style(theme.active_pane_border),
+        ),
+        Some(false) => (
+            theme.inactive_selected_entry,
+            ROUNDED_BLOCK
+                .title(pane.current_dir.to_string_lossy().to_string())
+                .border_style(theme.inactive_pane_border),
+        ),
+    };
+
+    if pane.long_listing {
+        draw_long_listing(frame, pane, item_lines, highlight_style, block, area);
+        return;
+    }
+    let dir_items_list = List::new(item_lines)
+        .highlight_style(highlight_style)
+        .block(block);
+    frame.render_stateful_widget(dir_items_list, area, &mut pane.dir_items.state);
+}
+
+// every column is as wide as its widest cell, see `fit_column_widths` for when they don't fit
+fn draw_long_listing(
+    frame: &mut Frame,
+    pane: &mut Pane,
+    item_lines: Vec<Line<'static>>,
+    highlight_style: Style,
+    block: Block,
+    area: Rect,
+) {
+    let wanted_widths: Vec<u16> = pane
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(idx, column)| match column {
+            Column::Name => item_lines.iter().map(|x| x.width() as u16).max(),
+            _ => pane
+                .detail_cells
+                .iter()
+                .map(|x| x[idx].chars().count() as u16)
+                .max(),
+        })
+        .map(|x| x.unwrap_or(0))
+        .collect();
+    let column_widths =
+        columns::fit_column_widths(&pane.columns, &wanted_widths, block.inner(area).width);
+    let shown_columns: Vec<usize> = (0..pane.columns.len())
+        .filter(|x| column_widths[*x] > 0)
+        .collect();
+
+    let rows = item_lines
+        .into_iter()
+        .zip(&pane.detail_cells)
+        .map(|(name_line, cells)| {
+            Row::new(shown_columns.iter().map(|x| {
+                let column = pane.columns[*x];
+                match column {
+                    Column::Name => Cell::from(name_line.clone()),
+                    _ if column.is_right_aligned() => {
+                        Cell::from(Line::from(cells[*x].clone()).right_aligned())
+                    }
+                    _ => Cell::from(cells[*x].clone()),
+                }
+            }))
+        });
+    let table = Table::new(
+        rows,
+        shown_columns
+            .iter()
+            .map(|x| Constraint::Length(column_widths[*x])),
+    )
+    .column_spacing(columns::COLUMN_SPACING)
+    .row_highlight_style(highlight_style)
+    .block(block);
+
+    // the list state stays the one source of truth, so switching modes keeps the cursor
+    let mut table_state = TableState::default()
+        .with_offset(pane.dir_items.state.offset())
+        .with_selected(pane.dir_items.state.selected());
+    frame.render_stateful_widget(table, area, &mut table_state);
+    *pane.dir_items.state.offset_mut() = table_state.offset();
+}
+
+// returns the area of the current directory's column
+fn draw_miller_columns(
+    frame: &mut Frame,
+    pane: &mut Pane,
+    theme: &Theme,
+    show_icons: bool,
+    area: Rect,
+) -> Rect {
+    let column_sections = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Fill(2),
+        Constraint::Fill(2),
+    ])
+    .split(area);
+
+    let parent_items_list = List::new(dir_list_items(
+        &mut pane.parent_items,
+        theme,
+        show_icons,
+        &[],
+        &[],
+    ))
+    .highlight_style(theme.inactive_selected_entry)
+    .block(ROUNDED_BLOCK);
+    frame.render_stateful_widget(
+        parent_items_list,
+        column_sections[0],
+        &mut pane.parent_items.state,
+    );
+
+    draw_pane(frame, pane, theme, show_icons, column_sections[1], None);
+
+    pane.sync_preview(&theme.preview_syntax_theme);
+    draw_preview(
+        frame,
+        &mut pane.preview,
+        theme,
+        show_icons,
+        column_sections[2],
+    );
+    column_sections[1]
+}
+
+fn draw_preview(
+    frame: &mut Frame,
+    preview: &mut Preview,
+    theme: &Theme,
+    show_icons: bool,
+    area: Rect,
+) {
+    match &mut preview.kind {
+        PreviewKind::Dir(dir_items) => {
+            let child_items_list =
+                List::new(dir_list_items(dir_items, theme, show_icons, &[], &[]))
+                    .highlight_style(theme.inactive_selected_entry)
+                    .block(ROUNDED_BLOCK);
+            frame.render_stateful_widget(child_items_list, area, &mut dir_items.state);
+        }
+        PreviewKind::Text(lines) => {
+            let text = Text::from(lines.clone());
+            let scroll_offset = (preview.offset as u16, 0);
+            frame.render_widget(
+                Paragraph::new(text)
+                    .scroll(scroll_offset)
+                    .block(ROUNDED_BLOCK),
+                area,
+            );
+        }
+        // the offset and ascii columns are the first and last span of every line
+        PreviewKind::Hex(lines) => {
+            let text = Text::from_iter(lines.iter().cloned().map(|mut x| {
+                if let [offset, _, ascii] = x.spans.as_mut_slice() {
+                    offset.style = theme.hex_offset;
+                    ascii.style = theme.hex_ascii;
+                }
+                x
+            }));
+            frame.render_widget(Paragraph::new(text).block(ROUNDED_BLOCK), area);
+        }
+        PreviewKind::Empty => frame.render_widget(ROUNDED_BLOCK, area),
+    }
+}
+
+fn dir_list_items(
+    dir_list: &mut DirListState,
+    theme: &Theme,
+    show_icons: bool,
+    filter_matches: &[Vec<usize>],
+    prefixes: &[String],
+) -> Vec<ListItem<'static>> {
+    dir_item_lines(dir_list, theme, show_icons, filter_matches, prefixes)
+        .into_iter()
+        .map(ListItem::new)
+        .collect()
+}
+
+// characters matching the filter are highlighted so it is clear why an entry was kept
+fn dir_item_lines(
+    dir_list: &mut DirListState,
+    theme: &Theme,
+    show_icons: bool,
+    filter_matches: &[Vec<usize>],
+    prefixes: &[String],
+) -> Vec<Line<'static>> {
+    if show_icons {
+        dir_list.load_icons();
+    }
+    dir_list
+        .items
+        .iter()
+        .zip(&dir_list.styles)
+        .enumerate()
+        .map(|(idx, (x, entry_style))| {
+            let filename = x.file_name().to_string_lossy().to_string();
+            let mut item_line = match filter_matches.get(idx) {
+                Some(matched_indices) if !matched_indices.is_empty() => {
+                    highlight_matches(&filename, matched_indices, theme)
+                }
+                _ => Line::raw(filename),
+            };
+            let icon = dir_list.icons.get(idx).copied().flatten();
+            if let Some(icon) = icon.filter(|_| show_icons) {
+                item_line.spans.insert(0, Span::raw(format!("{icon} ")));
+            }
+            item_line
+                .spans
+                .iter_mut()
+                .for_each(|span| span.style = entry_style.patch(span.style));
+            if let Some(prefix) = prefixes.get(idx) {
+                item_line.spans.insert(0, Span::raw(prefix.clone()));
+            }
+            item_line
+        })
+        .collect()
+}
+
+fn highlight_matches(text: &str, matched_indices: &[usize], theme: &Theme) -> Line<'static> {
+    Line::from_iter(text.chars().enumerate().map(|(idx, c)| {
+        if matched_indices.contains(&idx) {
+            Span::styled(c.to_string(), theme.filter_match)
+        } else {
+            Span::raw(c.to_string())
+        }
+    }))
 }